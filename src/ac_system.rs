@@ -7,19 +7,49 @@ use serde::{Deserialize, Serialize};
 use sparsetools::{csc::CSC, dok::DoK};
 use spsolve::FactorSolver;
 
-use crate::busbar::{Busbar, BusbarIndex};
-use crate::cable::Cable;
+use crate::busbar::{c_or_default, Busbar, BusbarIndex};
+use crate::cable::{Cable, CableThermalCheck};
+use crate::capacitor::SeriesCapacitor;
+use crate::cmplx;
+use crate::contribution::{transformer_limit, MotorContribution, MotorCriterion};
+use crate::converter::FullConverterUnit;
+use crate::drive::ConverterDrive;
+use crate::earth::{CapacitiveEarthFault, CompensationCoil, DoubleEarthFault, EarthReturn};
+use crate::factors::{dc_frequency_ratio, idc, kappa};
 use crate::fault::Fault;
 use crate::feeder::NetworkFeeder;
 use crate::generator::SynchronousGenerator;
-use crate::line::OverheadLine;
+use crate::line::{LineCoupling, OverheadLine};
+use crate::load::Load;
+use crate::math::{invert, ONE, SQRT_3};
 use crate::motor::AsynchronousMotor;
+use crate::motor_group::LowVoltageMotorGroup;
+use crate::per_unit::PerUnit;
+use crate::prefault::{OperatingPoint, SuperpositionFault};
+use crate::rating::{Branch, EquipmentRating, RatingEvaluation};
 use crate::reactor::Reactor;
 use crate::station::PowerStationUnit;
-use crate::transformer::NetworkTransformer;
+use crate::synchronous_motor::SynchronousMotor;
+use crate::tap::TapSelection;
+use crate::traits::CurrentSource;
+use crate::transformer::{NetworkTransformer, ZeroSequenceConnection};
 use crate::transformer3::ThreeWindingTransformer;
+use crate::waveform::Waveform;
+use crate::wind::{DoublyFedWindUnit, FullConverterWindUnit};
 
-const ONE: Complex64 = Complex64 { re: 1.0, im: 0.0 };
+/// Admittance (S) connecting each node to earth in the zero-sequence system, so that parts
+/// of the network without a path to earth do not make the admittance matrix singular.
+const ISOLATED_ADMITTANCE: f64 = 1e-9;
+
+/// Impedance (Ohms) of a bypassed series capacitor.
+const BYPASS_IMPEDANCE: f64 = 1e-6;
+
+/// Difference between the nominal system voltage and the rated voltage of a generator (kV)
+/// below which the generator is assumed to be operated at rated voltage.
+const GENERATOR_VOLTAGE_TOLERANCE: f64 = 1.0;
+
+/// Indexes of mutually coupled lines and their zero-sequence impedance matrix (Ohms).
+type CouplingGroup = (Vec<usize>, Vec<Vec<Complex64>>);
 
 #[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
 #[builder(default, setter(into))]
@@ -27,6 +57,15 @@ pub struct ACSystem<N: Clone + Default> {
     /// System nominal frequency.
     pub frequency: f64,
 
+    /// Neglect the asynchronous motors and low-voltage motor groups at the short-circuit
+    /// locations where their contribution meets the criteria (29) or (30).
+    pub neglect_motors: bool,
+
+    /// Position of the on-load tap changers of the transformers in the calculations with the
+    /// equivalent voltage source. The highest or lowest position may be selected for the
+    /// maximum and minimum short-circuit currents.
+    pub taps: TapSelection,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[builder(setter(each(name = "busbar")))]
     pub busbars: Vec<Busbar<N>>,
@@ -51,6 +90,30 @@ pub struct ACSystem<N: Clone + Default> {
     #[builder(setter(each(name = "motor")))]
     pub motors: Vec<AsynchronousMotor<N>>,
 
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "motor_group")))]
+    pub motor_groups: Vec<LowVoltageMotorGroup<N>>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "converter_drive")))]
+    pub converter_drives: Vec<ConverterDrive<N>>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "synchronous_motor")))]
+    pub synchronous_motors: Vec<SynchronousMotor<N>>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "doubly_fed_wind_unit")))]
+    pub doubly_fed_wind_units: Vec<DoublyFedWindUnit<N>>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "full_converter_wind_unit")))]
+    pub full_converter_wind_units: Vec<FullConverterWindUnit<N>>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "full_converter_unit")))]
+    pub full_converter_units: Vec<FullConverterUnit<N>>,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[builder(setter(each(name = "reactor")))]
     pub reactors: Vec<Reactor<N>>,
@@ -59,14 +122,32 @@ pub struct ACSystem<N: Clone + Default> {
     #[builder(setter(each(name = "line")))]
     pub lines: Vec<OverheadLine<N>>,
 
+    /// Mutual zero-sequence couplings between the lines.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "coupling")))]
+    pub couplings: Vec<LineCoupling>,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[builder(setter(each(name = "cable")))]
     pub cables: Vec<Cable<N>>,
 
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "series_capacitor")))]
+    pub series_capacitors: Vec<SeriesCapacitor<N>>,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[builder(setter(each(name = "three_winding_transformer")))]
     pub three_winding_transformers: Vec<ThreeWindingTransformer<N>>,
 
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "load")))]
+    pub loads: Vec<Load<N>>,
+
+    /// Short-circuit ratings of the equipment at the busbars and in the branches.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "rating")))]
+    pub ratings: Vec<EquipmentRating<N>>,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[builder(setter(each(name = "fault")))]
     pub faults: Vec<Fault<N>>,
@@ -77,15 +158,35 @@ impl<N: Clone + Default + Eq + core::hash::Hash> ACSystem<N> {
         ACSystemBuilder::default()
     }
 
+    /// Short-circuit impedance (Ohms) at each node, referred to the nominal voltage of the
+    /// node. Transformers are represented at the transformation ratios of the tap positions
+    /// selected by `taps`.
     pub fn fault_impedance<F>(
         &self,
         solver: impl FactorSolver<usize, Complex64, F>,
-    ) -> Result<HashMap<N, Complex64>>
-    where
-        N: Clone + Default + Eq + core::hash::Hash,
-    {
+    ) -> Result<HashMap<N, Complex64>> {
+        self.fault_impedances(&solver, None)
+    }
+
+    /// Short-circuit impedance (p.u.) at each node on the given base. The impedances of the
+    /// elements are converted to p.u. with the base voltage of the node they are connected
+    /// to, using the nominal system voltages of the busbars.
+    pub fn fault_impedance_pu<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        base: &PerUnit,
+    ) -> Result<HashMap<N, Complex64>> {
+        self.fault_impedances(&solver, Some(base))
+    }
+
+    fn fault_impedances<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+        base: Option<&PerUnit>,
+    ) -> Result<HashMap<N, Complex64>> {
         let (ix, nn) = self.nodes();
-        let y_mat: CSC<usize, Complex64> = self.admittance_matrix(&ix, nn)?;
+        let y_mat: CSC<usize, Complex64> =
+            self.admittance_matrix(&ix, nn, Some(&self.all_motors()), None, base)?;
 
         let factors =
             solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())?;
@@ -106,148 +207,2222 @@ impl<N: Clone + Default + Eq + core::hash::Hash> ACSystem<N> {
         Ok(zk)
     }
 
-    fn admittance_matrix(
+    /// Evaluates, for a short circuit at each busbar, if the contribution of the asynchronous
+    /// motors and low-voltage motor groups may be neglected. Motors are grouped by the
+    /// galvanically connected part of the network they are connected to. Results are keyed by
+    /// the first node of each busbar outside the auxiliary networks of power station units.
+    pub fn motor_contributions<F>(
         &self,
-        ix: &HashMap<N, usize>,
-        nn: usize,
-    ) -> Result<CSC<usize, Complex64>> {
-        let ib = BusbarIndex::new(&self.busbars);
+        solver: impl FactorSolver<usize, Complex64, F>,
+    ) -> Result<HashMap<N, Vec<MotorContribution>>> {
+        self.contributions(&solver)
+    }
 
-        let mut y_mat = DoK::new(nn, nn);
+    fn contributions<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+    ) -> Result<HashMap<N, Vec<MotorContribution>>> {
+        let (ix, nn) = self.nodes();
+        let y_mat = self.admittance_matrix(&ix, nn, None, None, None)?;
 
-        for (i, f) in self.feeders.iter().enumerate() {
-            let z = match f.impedance(false, &ib) {
-                Ok(z) => z,
-                Err(err) => {
-                    return Err(format_err!("feeder {} error: {}", i + 1, err));
-                }
-            };
-            if z == Complex64::default() {
-                return Err(format_err!("feeder {} error: zero impedance", i + 1));
+        let factors =
+            solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())?;
+
+        let island = self.islands(&ix, nn);
+
+        // Motor groups with the sum of their rated currents (kA) and active power (MW).
+        let mut groups: HashMap<usize, (MotorContribution, f64, f64)> = HashMap::new();
+        for (i, m) in self.motors.iter().enumerate() {
+            let g = groups.entry(island[ix[&m.node]]).or_default();
+            g.0.motors.push(i);
+            g.1 += m.ir();
+            g.2 += m.pr * m.n as f64 / 1e3;
+        }
+        for (i, m) in self.motor_groups.iter().enumerate() {
+            let g = groups.entry(island[ix[&m.node]]).or_default();
+            g.0.motor_groups.push(i);
+            g.1 += m.ir();
+            if m.prm.is_empty() {
+                return Err(format_err!(
+                    "motor group {} error: rated active power must be specified",
+                    i + 1
+                ));
             }
-            let j: usize = ix[&f.node];
-            y_mat.add(j, j, ONE / z)?;
+            g.2 += m.pr() / 1e3;
         }
+        let mut group_islands: Vec<usize> = groups.keys().cloned().collect();
+        group_islands.sort_unstable();
 
-        for (i, t) in self.transformers.iter().enumerate() {
-            let z = match t.impedance(false, &ib) {
-                Ok(z) => z,
-                Err(err) => {
-                    return Err(format_err!("transformer {} error: {}", i + 1, err));
-                }
+        let mut contributions = HashMap::new();
+        for busbar in &self.busbars {
+            let Some(node) = busbar.nodes.first() else {
+                continue;
             };
-            if z == Complex64::default() {
-                return Err(format_err!("transformer {} error: zero impedance", i + 1));
+            let Some(&k) = ix.get(node) else {
+                continue;
+            };
+
+            let mut z = vec![Complex64::default(); nn];
+            z[k] = ONE;
+            solver.solve(&factors, &mut z, false)?;
+
+            let c = c_or_default(busbar);
+            let ikss = (c * busbar.un) / (SQRT_3 * z[k].norm());
+
+            let mut motor_groups = vec![];
+            for g in &group_islands {
+                let (mut group, sum_irm, sum_prm) = groups[g].clone();
+
+                group.criterion = if *g == island[k] {
+                    MotorCriterion::RatedCurrent { sum_irm, ikss } // (29)
+                } else {
+                    let sum_srt: f64 = self
+                        .transformers
+                        .iter()
+                        .filter(|t| {
+                            let (hv, lv) = (island[ix[&t.node_hv]], island[ix[&t.node_lv]]);
+                            (hv == island[k] && lv == *g) || (hv == *g && lv == island[k])
+                        })
+                        .map(|t| t.sr / 1e3)
+                        .sum();
+
+                    if sum_srt == 0.0 {
+                        MotorCriterion::NotApplicable
+                    } else {
+                        let limit = transformer_limit(c, sum_srt, busbar.un, ikss);
+
+                        MotorCriterion::Transformers {
+                            sum_prm,
+                            sum_srt,
+                            limit,
+                        }
+                    }
+                };
+                group.included = !group.criterion.neglect();
+
+                motor_groups.push(group);
             }
-            let j = ix[&t.node_hv];
-            let k = ix[&t.node_lv];
-            let y = ONE / z;
+            contributions.insert(node.clone(), motor_groups);
+        }
+        Ok(contributions)
+    }
+
+    /// Calculates the short-circuit currents of a balanced three-phase short circuit at each
+    /// busbar using the equivalent voltage source at the short-circuit location. The minimum
+    /// time delay `tmin` (s) is used for the symmetrical short-circuit breaking current.
+    ///
+    /// The steady-state short-circuit current of a short circuit fed from a single network
+    /// feeder, synchronous machine or wind power station unit is obtained from its partial
+    /// current, asynchronous motors do not contribute. With several sources the network is
+    /// treated as meshed and the steady-state current is the initial symmetrical short-circuit
+    /// current calculated without the asynchronous motors and converter drives.
+    ///
+    /// If `neglect_motors` is set, asynchronous motors and low-voltage motor groups are
+    /// neglected at the short-circuit locations where their contribution meets the
+    /// criteria of [`motor_contributions`](ACSystem::motor_contributions).
+    ///
+    /// The peak short-circuit current is calculated from the R/X ratio at the short-circuit
+    /// location (method B, `ip50`) and using the equivalent frequency (method C, `ip20`).
+    /// The source currents of power station units with full size converter are superimposed
+    /// on the solution of the equivalent voltage source, using the transfer impedances between
+    /// the source nodes and the short-circuit location.
+    ///
+    /// The asymmetrical breaking current combines the breaking current with the d.c.
+    /// component at `tmin`, with the R/X ratio from the equivalent frequency method. The
+    /// minimum time delay `tmin` (s) must be positive.
+    ///
+    /// Series capacitors protected by a varistor or a bypass switch are neglected. Series
+    /// capacitors without protection are included with their reactance.
+    pub fn short_circuit<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        tmin: f64,
+    ) -> Result<Vec<Fault<N>>> {
+        self.short_circuits(&solver, tmin)
+    }
+
+    /// Short-circuit currents (p.u.) of a balanced three-phase short circuit at each busbar on
+    /// the given base, calculated as in [`short_circuit`](ACSystem::short_circuit). The base
+    /// current at each busbar is taken at its nominal system voltage.
+    pub fn short_circuit_pu<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        tmin: f64,
+        base: &PerUnit,
+    ) -> Result<Vec<Fault<N>>> {
+        let busbar_index = BusbarIndex::new(&self.busbars);
+
+        let faults = self.short_circuits(&solver, tmin)?;
+
+        faults
+            .iter()
+            .map(|f| match busbar_index.busbar(&f.node) {
+                Some(busbar) => Ok(base.fault(f, busbar.un)),
+                None => Err(format_err!("fault error: node not on a busbar")),
+            })
+            .collect()
+    }
+
+    /// Short-circuit currents of a balanced three-phase short circuit at each busbar,
+    /// calculated as in [`short_circuit`](ACSystem::short_circuit), with the thermal
+    /// equivalent short-circuit current for the duration of the short-circuit current `tk`
+    /// (s). The thermal equivalent current is obtained from the waveform of the short-circuit
+    /// current.
+    pub fn thermal_short_circuit<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        tmin: f64,
+        tk: f64,
+    ) -> Result<Vec<Fault<N>>> {
+        self.thermal_short_circuits(&solver, tmin, tk)
+    }
 
-            y_mat.add(j, j, y)?;
-            y_mat.sub(j, k, y)?;
-            y_mat.sub(k, j, y)?;
-            y_mat.add(k, k, y)?;
+    fn thermal_short_circuits<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+        tmin: f64,
+        tk: f64,
+    ) -> Result<Vec<Fault<N>>> {
+        let mut faults = self.short_circuits(solver, tmin)?;
+        for fault in &mut faults {
+            fault.ith = self.fault_waveform(fault, tmin).ith(tk);
         }
+        Ok(faults)
+    }
 
-        for (i, c) in self.cables.iter().enumerate() {
-            let z = match c.impedance() {
-                Ok(z) => z,
-                Err(err) => {
-                    return Err(format_err!("cable {} error: {}", i + 1, err));
+    fn short_circuits<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+        tmin: f64,
+    ) -> Result<Vec<Fault<N>>> {
+        if tmin <= 0.0 {
+            return Err(format_err!("minimum time delay must be positive"));
+        }
+        let (ix, nn) = self.nodes();
+        let busbar_index = BusbarIndex::new(&self.busbars);
+        let un = self.nominal_voltages(&ix, nn);
+
+        let fc_f = 0.4; // 20 Hz for 50 Hz systems, 24 Hz for 60 Hz systems.
+
+        // Equivalent frequency for the d.c. component at the minimum time delay.
+        let fc_dc = dc_frequency_ratio(self.frequency, tmin);
+
+        // Admittance matrices for the initial, peak (methods B and C) and d.c. components
+        // with the motors that are not neglected at the short-circuit location.
+        let factor = |motors: &MotorSelection| -> Result<[F; 4]> {
+            let factor = |peak: Option<f64>| -> Result<F> {
+                let y_mat = self.admittance_matrix(&ix, nn, Some(motors), peak, None)?;
+                solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())
+            };
+            Ok([
+                factor(None)?,
+                factor(Some(1.0))?,
+                factor(Some(fc_f))?,
+                factor(Some(fc_dc))?,
+            ])
+        };
+        let contributions = if self.neglect_motors {
+            self.contributions(solver)?
+        } else {
+            HashMap::new()
+        };
+        let mut selections: Vec<(MotorSelection, [F; 4])> = vec![];
+
+        // Admittance matrix without motors for the steady-state current in meshed networks.
+        let factors_0 = {
+            let y_mat = self.admittance_matrix(&ix, nn, None, None, None)?;
+            solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())?
+        };
+
+        let solve = |factors: &F, k: usize| -> Result<Vec<Complex64>> {
+            let mut z = vec![Complex64::default(); nn];
+            z[k] = ONE;
+            solver.solve(factors, &mut z, false)?;
+            Ok(z)
+        };
+
+        let mut faults = vec![];
+        for busbar in &self.busbars {
+            let Some(node) = busbar.nodes.first() else {
+                continue;
+            };
+
+            // Short circuit between a generator and its unit transformer.
+            if let Some(i) = self
+                .power_stations
+                .iter()
+                .position(|s| busbar.nodes.contains(&s.generator.node))
+            {
+                faults.push(self.station_terminal_fault(solver, i, busbar, tmin)?);
+                continue;
+            }
+            // Short circuit in the auxiliary network of a power station unit.
+            if let Some(i) = self.power_stations.iter().position(|s| {
+                s.auxiliary_nodes()
+                    .iter()
+                    .any(|&n| busbar.nodes.contains(n))
+            }) {
+                faults.push(self.station_auxiliary_fault(solver, i, busbar, tmin)?);
+                continue;
+            }
+            let k = ix[node];
+
+            // (29) (30)
+            let mut motors = self.all_motors();
+            for group in contributions.get(node).into_iter().flatten() {
+                for &i in &group.motors {
+                    motors.motors[i] = group.included;
+                }
+                for &i in &group.motor_groups {
+                    motors.motor_groups[i] = group.included;
+                }
+            }
+            let s = match selections.iter().position(|(m, _)| *m == motors) {
+                Some(s) => s,
+                None => {
+                    selections.push((motors.clone(), factor(&motors)?));
+                    selections.len() - 1
                 }
             };
-            if z == Complex64::default() {
-                return Err(format_err!("cable {} error: zero impedance", i + 1));
+            let [factors, factors_b, factors_c, factors_dc] = &selections[s].1;
+
+            let z = solve(factors, k)?;
+
+            let c = c_or_default(busbar);
+            let ikss = cmplx!(c * busbar.un / SQRT_3) / z[k]; // kA
+
+            // Voltage at the terminals of a machine relative to the equivalent voltage
+            // source, the partial short-circuit current of the machine (kA) and the ratio
+            // for referring the partial current to the short-circuit location.
+            let partial = |n: &N, zm: Complex64| {
+                let un = un[ix[n]];
+                let du = z[ix[n]] * ikss; // kV
+                (
+                    du.norm() / (c * un / SQRT_3),
+                    (du / zm).norm(),
+                    un / busbar.un,
+                )
+            };
+
+            // Sources connected to the short-circuit location, other than motors and current
+            // sources.
+            let sources = self
+                .feeders
+                .iter()
+                .map(|f| &f.node)
+                .chain(self.generators.iter().map(|g| &g.node))
+                .chain(self.power_stations.iter().map(|s| &s.generator.node))
+                .chain(self.synchronous_motors.iter().map(|m| &m.node))
+                .chain(self.doubly_fed_wind_units.iter().map(|w| &w.node))
+                .filter(|&n| z[ix[n]] != Complex64::default())
+                .count();
+            let single_fed = sources == 1;
+
+            // (75)
+            let mut ib = ikss.norm();
+            let mut ik = ikss.norm();
+            for (m, _) in self.motors.iter().zip(&motors.motors).filter(|m| *m.1) {
+                let (du, ikss_m, tr) = partial(&m.node, m.impedance()?);
+                ib -= du * (ikss_m - m.breaking_current(ikss_m, tmin)) * tr;
+                ik -= ikss_m * tr;
+            }
+            for (m, _) in self
+                .motor_groups
+                .iter()
+                .zip(&motors.motor_groups)
+                .filter(|m| *m.1)
+            {
+                let (du, ikss_m, tr) = partial(&m.node, m.impedance()?);
+                ib -= du * (ikss_m - m.breaking_current(ikss_m, tmin)) * tr;
+                ik -= ikss_m * tr;
+            }
+            for d in &self.converter_drives {
+                let (du, ikss_d, tr) = partial(&d.node, d.impedance()?);
+                ib -= du * (ikss_d - d.breaking_current(ikss_d, tmin)) * tr;
+                ik -= ikss_d * tr;
+            }
+            for g in &self.generators {
+                let zg = g.impedance(GENERATOR_VOLTAGE_TOLERANCE, false, &busbar_index)?;
+                let (du, ikss_g, tr) = partial(&g.node, zg);
+                ib -= du * (ikss_g - g.breaking_current(ikss_g, tmin)) * tr;
+                if single_fed {
+                    ik -= (ikss_g - g.steady_state_current(ikss_g)) * tr;
+                }
+            }
+            for s in &self.power_stations {
+                let (zg, _) = s.impedances(false, false, &busbar_index)?;
+                let (du, ikss_g, tr) = partial(&s.generator.node, zg);
+                let g = &s.generator;
+
+                ib -= du * (ikss_g - g.breaking_current(ikss_g, tmin)) * tr;
+                if single_fed {
+                    ik -= (ikss_g - g.steady_state_current(ikss_g)) * tr;
+                }
+            }
+            for m in &self.synchronous_motors {
+                let (du, ikss_m, tr) = partial(&m.node, m.impedance(false, &busbar_index)?);
+                ib -= du * (ikss_m - m.breaking_current(ikss_m, tmin)) * tr;
+                if single_fed {
+                    ik -= (ikss_m - m.steady_state_current(ikss_m)) * tr;
+                }
+            }
+            for w in &self.doubly_fed_wind_units {
+                let (_, ikss_w, tr) = partial(&w.node, w.impedance()?);
+                if single_fed {
+                    ik -= (ikss_w - w.steady_state_current()) * tr;
+                }
             }
-            let j = ix[&c.node_i];
-            let k = ix[&c.node_j];
-            let y = ONE / z;
+            if !single_fed {
+                // Meshed network: initial short-circuit current without motors.
+                ik = (c * busbar.un) / (SQRT_3 * solve(&factors_0, k)?[k].norm());
+            }
+
+            // Peak short-circuit current.
+            let zk_b = solve(factors_b, k)?[k];
+            let kappa_max = if busbar.un <= 1.0 { 1.8 } else { 2.0 };
+            let kappa_b = (1.15 * kappa(zk_b.re / zk_b.im)).min(kappa_max); // (59)
+            let mut ip50 = kappa_b * 2.0f64.sqrt() * ikss.norm();
+
+            let zk_c = solve(factors_c, k)?[k];
+            let kappa_c = kappa((zk_c.re / zk_c.im) * fc_f); // (60)
+            let mut ip20 = kappa_c * 2.0f64.sqrt() * ikss.norm();
+
+            // D.c. component at the minimum time delay, without the current sources.
+            let zk_dc = solve(factors_dc, k)?[k];
+            let rx_dc = (zk_dc.re / zk_dc.im) * fc_dc;
+            let idc = idc(ikss.norm(), self.frequency, tmin, rx_dc);
 
-            y_mat.add(j, j, y)?;
-            y_mat.sub(j, k, y)?;
-            y_mat.sub(k, j, y)?;
-            y_mat.add(k, k, y)?;
+            // (35)
+            let mut ikss = ikss.norm();
+            for s in self.current_sources() {
+                let zr = z[ix[s.node()]].norm() / z[k].norm();
+
+                ikss += zr * s.isk();
+                ib += zr * s.isk();
+                ik += zr * s.ikmax();
+                ip50 += zr * 2.0f64.sqrt() * s.isk();
+                ip20 += zr * 2.0f64.sqrt() * s.isk();
+            }
+
+            faults.push(Fault {
+                node: node.clone(),
+                ikss,
+                ip50,
+                ip20,
+                ib,
+                ib_asym: (ib * ib + idc * idc).sqrt(),
+                idc,
+                rx: rx_dc,
+                ik,
+                ..Default::default()
+            });
         }
+        Ok(faults)
+    }
 
-        for (i, l) in self.lines.iter().enumerate() {
-            let z = match l.impedance(self.frequency) {
-                Ok(z) => z,
-                Err(err) => {
-                    return Err(format_err!("line {} error: {}", i + 1, err));
+    /// Instantaneous short-circuit current of a three-phase short circuit at the busbar with
+    /// the given node. The a.c. component is given by the short-circuit currents with the
+    /// minimum time delay `tmin` (s) and the d.c. component by the d.c. component of the
+    /// asymmetrical breaking current, decaying with its R/X ratio (method C).
+    pub fn waveform<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        node: &N,
+        tmin: f64,
+    ) -> Result<Waveform> {
+        let fault = self
+            .short_circuits(&solver, tmin)?
+            .into_iter()
+            .find(|f| self.same_busbar(&f.node, node))
+            .ok_or_else(|| format_err!("waveform error: node not on a busbar"))?;
+
+        Ok(self.fault_waveform(&fault, tmin))
+    }
+
+    /// Compares the short-circuit currents at the busbars with the ratings of the equipment
+    /// for the minimum time delay `tmin` (s) and the duration of the short-circuit current
+    /// `tk` (s). The rated breaking current is compared with the asymmetrical breaking
+    /// current, the peak current is the larger of methods B and C and the thermal
+    /// equivalent current is obtained from the waveform of the short-circuit current.
+    ///
+    /// The currents of equipment in a branch are the currents at the busbar scaled by the
+    /// larger share of the initial short-circuit current through the equipment, for a short
+    /// circuit on either side of it (see [`branch_share`](ACSystem::branch_share)).
+    pub fn evaluate_ratings<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        tmin: f64,
+        tk: f64,
+    ) -> Result<Vec<RatingEvaluation<N>>> {
+        let faults = self.thermal_short_circuits(&solver, tmin, tk)?;
+        let factors = if self.ratings.iter().any(|r| r.branch.is_some()) {
+            Some(self.share_factors(&solver)?)
+        } else {
+            None
+        };
+
+        let mut evaluations = vec![];
+        for (i, rating) in self.ratings.iter().enumerate() {
+            let Some(fault) = faults
+                .iter()
+                .find(|f| self.same_busbar(&f.node, &rating.node))
+            else {
+                return Err(format_err!("rating {} error: node not on a busbar", i + 1));
+            };
+            let r = match (rating.branch, &factors) {
+                (Some(branch), Some(factors)) => {
+                    match self.share(&solver, factors, &rating.node, branch) {
+                        Ok(r) => r,
+                        Err(err) => return Err(format_err!("rating {} error: {}", i + 1, err)),
+                    }
                 }
+                _ => 1.0,
             };
-            if z == Complex64::default() {
-                return Err(format_err!("line {}: zero impedance", i + 1));
-            }
-            let j = ix[&l.node_i];
-            let k = ix[&l.node_j];
-            let y = ONE / z;
-
-            y_mat.add(j, j, y)?;
-            y_mat.sub(j, k, y)?;
-            y_mat.sub(k, j, y)?;
-            y_mat.add(k, k, y)?;
+            evaluations.push(RatingEvaluation::new(
+                rating,
+                r * fault.ib,
+                r * fault.ib_asym,
+                r * fault.ip50.max(fault.ip20),
+                r * fault.ith,
+                tk,
+            ));
         }
+        Ok(evaluations)
+    }
 
-        Ok(y_mat.to_csc())
+    /// Share of the initial short-circuit current of a short circuit at the busbar with the
+    /// given node that flows through the end of the branch at the busbar. The larger of the
+    /// partial current of the branch, for a short circuit on the busbar side of the end, and
+    /// of the partial current of the rest of the network, for a short circuit on the branch
+    /// side, is returned.
+    pub fn branch_share<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        node: &N,
+        branch: Branch,
+    ) -> Result<f64> {
+        self.branch_shares(&solver, node, branch)
     }
 
-    fn nodes(&self) -> (HashMap<N, usize>, usize) {
-        let mut nodes = HashMap::new();
-        let mut n = 0;
+    fn branch_shares<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+        node: &N,
+        branch: Branch,
+    ) -> Result<f64> {
+        let factors = self.share_factors(solver)?;
+        self.share(solver, &factors, node, branch)
+    }
 
-        for (i, b) in self.busbars.iter().enumerate() {
-            // if b.node != "" {
-            //     nodes[b.Node] = i
-            // }
-            for t in &b.nodes {
-                nodes.insert(t.clone(), i);
-            }
-            n += 1;
+    /// Factorized admittance matrix for the shares of the branches.
+    fn share_factors<F>(&self, solver: &impl FactorSolver<usize, Complex64, F>) -> Result<F> {
+        let (ix, nn) = self.nodes();
+        let y_mat = self.admittance_matrix(&ix, nn, Some(&self.all_motors()), None, None)?;
+        solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())
+    }
+
+    fn share<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+        factors: &F,
+        node: &N,
+        branch: Branch,
+    ) -> Result<f64> {
+        let (ix, nn) = self.nodes();
+        let busbar_index = BusbarIndex::new(&self.busbars);
+
+        if !self.busbars.iter().any(|b| b.nodes.contains(node)) {
+            return Err(format_err!("node not on a busbar"));
         }
+        let Some(&k) = ix.get(node) else {
+            return Err(format_err!(
+                "node on an auxiliary busbar of a power station unit"
+            ));
+        };
+        let mut z = vec![Complex64::default(); nn];
+        z[k] = ONE;
+        solver.solve(factors, &mut z, false)?;
 
-        let mut add = |t: &N| {
-            if !nodes.contains_key(t) {
-                nodes.insert(t.clone(), n);
-                n += 1;
+        // Partial current flowing into node k through a branch from node j with impedance zb,
+        // per unit of the short-circuit current at k.
+        let partial = |j: usize, zb: Complex64| (z[k] - z[j]) / zb;
+
+        let rho = match branch {
+            Branch::Line(i) => {
+                let l = self
+                    .lines
+                    .get(i)
+                    .ok_or_else(|| format_err!("line {} not found", i + 1))?;
+                let zl = l.impedance(self.frequency)?;
+                match (ix[&l.node_i], ix[&l.node_j]) {
+                    (i, j) if i == k => partial(j, zl),
+                    (i, j) if j == k => partial(i, zl),
+                    _ => return Err(format_err!("line {} not at the busbar", i + 1)),
+                }
+            }
+            Branch::Cable(i) => {
+                let c = self
+                    .cables
+                    .get(i)
+                    .ok_or_else(|| format_err!("cable {} not found", i + 1))?;
+                let zc = c.unreferred_impedance()?;
+                match (ix[&c.node_i], ix[&c.node_j]) {
+                    (i, j) if i == k => partial(j, zc),
+                    (i, j) if j == k => partial(i, zc),
+                    _ => return Err(format_err!("cable {} not at the busbar", i + 1)),
+                }
+            }
+            Branch::Transformer(i) => {
+                let t = self
+                    .transformers
+                    .get(i)
+                    .ok_or_else(|| format_err!("transformer {} not found", i + 1))?;
+                let zt = t.impedance(false, &busbar_index)?;
+                let n = t.ratio(self.taps);
+                match (ix[&t.node_hv], ix[&t.node_lv]) {
+                    // Current on the high-voltage side, the current on the low-voltage side
+                    // divided by the ratio.
+                    (hv, lv) if hv == k => (z[hv] / n - z[lv]) / (zt * n),
+                    (hv, lv) if lv == k => (z[lv] - z[hv] / n) / zt,
+                    _ => return Err(format_err!("transformer {} not at the busbar", i + 1)),
+                }
             }
         };
+        Ok(rho.norm().max((ONE - rho).norm()))
+    }
 
-        for f in &self.feeders {
-            add(&f.node);
-        }
-        for s in &self.power_stations {
-            // if s.Generator != nil {
-            add(&s.generator.node);
-            // }
-            // if s.Transformer != nil {
-            add(&s.transformer.node_hv);
-            add(&s.transformer.node_lv);
-            // }
-        }
-        for g in &self.generators {
-            add(&g.node);
-        }
-        for t in &self.transformers {
-            add(&t.node_hv);
-            add(&t.node_lv);
+    /// Checks the thermal withstand of the conductors of each cable for the duration of the
+    /// short-circuit current `tk` (s), with the minimum time delay `tmin` (s).
+    ///
+    /// The thermal equivalent short-circuit current through the cable is the thermal
+    /// equivalent current at the busbar at an end, scaled by the share of the cable (see
+    /// [`branch_share`](ACSystem::branch_share)). The cable is checked for the larger of the
+    /// currents at its ends on a busbar, such as the feeding end of a cable to the terminals
+    /// of a motor.
+    pub fn cable_thermal_checks<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        tmin: f64,
+        tk: f64,
+    ) -> Result<Vec<CableThermalCheck>> {
+        if self.cables.is_empty() {
+            return Ok(vec![]);
         }
-        for t in &self.three_winding_transformers {
-            add(&t.node_hv);
-            add(&t.node_mv);
-            add(&t.node_lv);
+        let faults = self.thermal_short_circuits(&solver, tmin, tk)?;
+        let factors = self.share_factors(&solver)?;
+
+        let mut checks = vec![];
+        for (i, c) in self.cables.iter().enumerate() {
+            let mut ith: Option<f64> = None;
+            for node in [&c.node_i, &c.node_j] {
+                let Some(fault) = faults.iter().find(|f| self.same_busbar(&f.node, node)) else {
+                    continue;
+                };
+                let r = match self.share(&solver, &factors, node, Branch::Cable(i)) {
+                    Ok(r) => r,
+                    Err(err) => return Err(format_err!("cable {} error: {}", i + 1, err)),
+                };
+                ith = Some(ith.unwrap_or_default().max(r * fault.ith));
+            }
+            let Some(ith) = ith else {
+                return Err(format_err!("cable {} error: no node on a busbar", i + 1));
+            };
+            match c.thermal_check(ith, tk) {
+                Ok(check) => checks.push(check),
+                Err(err) => {
+                    return Err(format_err!("cable {} error: {}", i + 1, err));
+                }
+            }
         }
-        for m in &self.motors {
-            add(&m.node);
+        Ok(checks)
+    }
+
+    fn fault_waveform(&self, fault: &Fault<N>, tmin: f64) -> Waveform {
+        Waveform::new(fault, tmin, self.frequency)
+    }
+
+    /// Returns true if nodes `a` and `b` are on the same busbar.
+    fn same_busbar(&self, a: &N, b: &N) -> bool {
+        self.busbars
+            .iter()
+            .any(|busbar| busbar.nodes.contains(a) && busbar.nodes.contains(b))
+    }
+
+    /// Calculates the initial short-circuit current Ik1'' of a line-to-earth short circuit at
+    /// each busbar using the equivalent voltage source at the short-circuit location (52).
+    /// The negative-sequence impedance is taken equal to the positive-sequence impedance.
+    /// The peak current uses the factor κ of the three-phase short circuit (method B) and
+    /// the breaking and steady-state currents are taken equal to Ik1''.
+    ///
+    /// Synchronous machines and three-winding transformers are not represented in the
+    /// zero-sequence system.
+    pub fn line_to_earth<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+    ) -> Result<Vec<Fault<N>>> {
+        let (ix, nn) = self.nodes();
+
+        let factor = |y_mat: CSC<usize, Complex64>| -> Result<F> {
+            solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())
+        };
+        let factors =
+            factor(self.admittance_matrix(&ix, nn, Some(&self.all_motors()), None, None)?)?;
+        let factors_b =
+            factor(self.admittance_matrix(&ix, nn, Some(&self.all_motors()), Some(1.0), None)?)?;
+        let factors_0 = factor(self.zero_sequence_admittance_matrix(&ix, nn)?)?;
+
+        let solve = |factors: &F, k: usize| -> Result<Complex64> {
+            let mut z = vec![Complex64::default(); nn];
+            z[k] = ONE;
+            solver.solve(factors, &mut z, false)?;
+            Ok(z[k])
+        };
+
+        let mut faults = vec![];
+        for busbar in &self.busbars {
+            let Some(node) = busbar.nodes.first() else {
+                continue;
+            };
+            let Some(&k) = ix.get(node) else {
+                continue;
+            };
+            let z1 = solve(&factors, k)?;
+            let z0 = solve(&factors_0, k)?;
+
+            let c = c_or_default(busbar);
+            let ikss = SQRT_3 * c * busbar.un / (cmplx!(2) * z1 + z0).norm(); // (52)
+
+            let zk_b = solve(&factors_b, k)?;
+            let kappa_max = if busbar.un <= 1.0 { 1.8 } else { 2.0 };
+            let kappa_b = (1.15 * kappa(zk_b.re / zk_b.im)).min(kappa_max); // (59)
+
+            faults.push(Fault {
+                node: node.clone(),
+                ikss,
+                ip50: kappa_b * 2.0f64.sqrt() * ikss,
+                ib: ikss,
+                ik: ikss,
+                ..Default::default()
+            });
         }
+        Ok(faults)
+    }
+
+    /// Calculates the capacitive earth fault current and the residual current of a
+    /// line-to-earth fault at each busbar of an isolated network, or of a resonant-earthed
+    /// network if the compensation coil is given. The zero-sequence capacitances of the lines
+    /// and cables of the galvanically connected part of the network are summed and the
+    /// source voltage at the fault location is c Un / sqrt(3).
+    pub fn capacitive_earth_faults(
+        &self,
+        coil: Option<&CompensationCoil>,
+    ) -> Vec<CapacitiveEarthFault<N>> {
+        let (ix, nn) = self.nodes();
+        let island = self.islands(&ix, nn);
+
+        let mut c0 = vec![0.0; nn];
         for l in &self.lines {
-            add(&l.node_i);
-            add(&l.node_j);
+            c0[island[ix[&l.node_i]]] += l.capacitances().1;
         }
         for c in &self.cables {
+            c0[island[ix[&c.node_i]]] += c.capacitances().1;
+        }
+
+        let mut faults = vec![];
+        for busbar in &self.busbars {
+            let Some(node) = busbar.nodes.first() else {
+                continue;
+            };
+            let Some(&k) = ix.get(node) else {
+                continue;
+            };
+            faults.push(CapacitiveEarthFault::new(
+                node.clone(),
+                c0[island[k]],
+                c_or_default(busbar),
+                busbar.un,
+                self.frequency,
+                coil,
+            ));
+        }
+        faults
+    }
+
+    /// Calculates the initial short-circuit current Ik,EE'' of a double earth fault at the
+    /// busbar nodes `node_a` and `node_b` of an isolated or resonant-earthed network from the
+    /// positive and zero-sequence driving point and transfer impedances between the nodes
+    /// (IEC 60909-3). The negative-sequence impedances are taken equal to the positive-sequence
+    /// impedances and the capacitances of the network are neglected.
+    ///
+    /// The partial short-circuit currents of the lines and cables are the zero-sequence
+    /// currents 3I0 between the fault locations, split between the earth and the earth wires
+    /// or cable sheaths with their reduction factors.
+    pub fn double_earth_fault<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        node_a: &N,
+        node_b: &N,
+    ) -> Result<DoubleEarthFault<N>> {
+        let (ix, nn) = self.nodes();
+
+        let busbar = |node: &N| {
+            self.busbars
+                .iter()
+                .find(|b| b.nodes.contains(node))
+                .ok_or_else(|| format_err!("double earth fault error: node not on a busbar"))
+        };
+        let (busbar_a, _) = (busbar(node_a)?, busbar(node_b)?);
+        let (Some(&a), Some(&b)) = (ix.get(node_a), ix.get(node_b)) else {
+            return Err(format_err!(
+                "double earth fault error: node on an auxiliary busbar of a power station unit"
+            ));
+        };
+        if a == b {
+            return Err(format_err!(
+                "double earth fault error: fault locations on the same busbar"
+            ));
+        }
+        let island = self.islands(&ix, nn);
+        if island[a] != island[b] {
+            return Err(format_err!(
+                "double earth fault error: fault locations not galvanically connected"
+            ));
+        }
+
+        let factor = |y_mat: CSC<usize, Complex64>| -> Result<F> {
+            solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())
+        };
+        let factors =
+            factor(self.admittance_matrix(&ix, nn, Some(&self.all_motors()), None, None)?)?;
+        let factors_0 = factor(self.zero_sequence_admittance_matrix(&ix, nn)?)?;
+
+        let solve = |factors: &F, x: &[(usize, Complex64)]| -> Result<Vec<Complex64>> {
+            let mut z = vec![Complex64::default(); nn];
+            for &(k, v) in x {
+                z[k] = v;
+            }
+            solver.solve(factors, &mut z, false)?;
+            Ok(z)
+        };
+        let z1a = solve(&factors, &[(a, ONE)])?;
+        let z1b = solve(&factors, &[(b, ONE)])?;
+
+        // Zero-sequence voltages for a unit current injected at A and drawn at B. The
+        // difference avoids the large driving point impedances of the isolated network.
+        let z0ab = solve(&factors_0, &[(a, ONE), (b, -ONE)])?;
+
+        let z = cmplx!(2) * (z1a[a] + z1b[b] + z1a[b]) + z0ab[a] - z0ab[b];
+
+        let ikee = 3.0 * c_or_default(busbar_a) * busbar_a.un / z.norm();
+
+        // Zero-sequence current 3I0 through a branch with impedance z0 and the zero-sequence
+        // voltage difference dv between its nodes per unit fault current.
+        let i0 = |dv: Complex64, z0: Complex64| (dv / z0).norm() * ikee;
+
+        let mut z0 = vec![];
+        for l in &self.lines {
+            z0.push(l.zero_sequence_impedance(self.frequency)?);
+        }
+        let dv = |i: &N, j: &N| z0ab[ix[i]] - z0ab[ix[j]];
+
+        let mut lines = vec![Default::default(); self.lines.len()];
+        let mut coupled = vec![false; self.lines.len()];
+        for (group, z) in self.coupling_groups(&z0)? {
+            let y = invert(&z)?;
+            let dvs: Vec<Complex64> = group
+                .iter()
+                .map(|&i| dv(&self.lines[i].node_i, &self.lines[i].node_j))
+                .collect();
+            for (p, &i) in group.iter().enumerate() {
+                let ip: Complex64 = y[p].iter().zip(&dvs).map(|(y, dv)| y * dv).sum();
+                let l = &self.lines[i];
+                lines[i] = EarthReturn::new(ip.norm() * ikee, l.reduction_factor(self.frequency)?);
+                coupled[i] = true;
+            }
+        }
+        for (i, l) in self.lines.iter().enumerate() {
+            if !coupled[i] {
+                let ik = i0(dv(&l.node_i, &l.node_j), z0[i]);
+                lines[i] = EarthReturn::new(ik, l.reduction_factor(self.frequency)?);
+            }
+        }
+
+        let mut cables = vec![];
+        for c in &self.cables {
+            let ik = i0(dv(&c.node_i, &c.node_j), c.zero_sequence_impedance()?);
+            cables.push(EarthReturn::new(ik, c.reduction_factor(self.frequency)));
+        }
+
+        Ok(DoubleEarthFault {
+            node_a: node_a.clone(),
+            node_b: node_b.clone(),
+            ikee,
+            lines,
+            cables,
+        })
+    }
+
+    /// Solves the balanced load flow for the loads of the system and the injections of the
+    /// given operating point and returns the pre-fault line-to-line voltage (kV) of each node.
+    /// Network feeders are represented by their source voltage behind the short-circuit
+    /// impedance and elements are modelled with their impedances without correction factors.
+    pub fn load_flow<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        op: &OperatingPoint<N>,
+    ) -> Result<HashMap<N, Complex64>> {
+        let (ix, nn) = self.nodes();
+
+        let v = self.prefault_voltages(&solver, &ix, nn, op)?;
+
+        Ok(ix
+            .iter()
+            .map(|(n, &i)| (n.clone(), v[i] * SQRT_3))
+            .collect())
+    }
+
+    /// Calculates the initial symmetrical and peak short-circuit currents at each busbar
+    /// with the superposition method. The pre-fault voltage at the short-circuit location
+    /// is taken from the load flow solution, or from the voltages of the operating point,
+    /// and the machines are represented by their subtransient impedances without
+    /// correction factors. The peak current is calculated from the R/X ratio at the
+    /// short-circuit location (method B). The symmetrical short-circuit breaking current and
+    /// the steady-state short-circuit current are not calculated.
+    pub fn superposition<F>(
+        &self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        op: &OperatingPoint<N>,
+    ) -> Result<Vec<SuperpositionFault<N>>> {
+        let (ix, nn) = self.nodes();
+
+        let v = self.prefault_voltages(&solver, &ix, nn, op)?;
+
+        let y_mat = self.operating_admittance_matrix(&ix, nn, true)?;
+        let factors =
+            solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())?;
+
+        let mut faults = vec![];
+        for busbar in &self.busbars {
+            let Some(node) = busbar.nodes.first() else {
+                continue;
+            };
+            let Some(&k) = ix.get(node) else {
+                continue;
+            };
+
+            let mut z = vec![Complex64::default(); nn];
+            z[k] = ONE;
+            solver.solve(&factors, &mut z, false)?;
+
+            let ikss = (v[k] / z[k]).norm(); // kA
+
+            let kappa_max = if busbar.un <= 1.0 { 1.8 } else { 2.0 };
+            let kappa_b = (1.15 * kappa(z[k].re / z[k].im)).min(kappa_max); // (59)
+            let ip = kappa_b * 2.0f64.sqrt() * ikss;
+
+            faults.push(SuperpositionFault {
+                node: node.clone(),
+                ub: v[k].norm() * SQRT_3,
+                ikss,
+                ip,
+            });
+        }
+        Ok(faults)
+    }
+
+    /// Sets the operating conditions of the network transformers before the short circuit
+    /// from the load flow solution for the given operating point. The correction factors
+    /// of the transformers are then calculated with (12b).
+    pub fn set_transformer_operating_conditions<F>(
+        &mut self,
+        solver: impl FactorSolver<usize, Complex64, F>,
+        op: &OperatingPoint<N>,
+    ) -> Result<()> {
+        let (ix, nn) = self.nodes();
+
+        let v = self.prefault_voltages(&solver, &ix, nn, op)?;
+
+        for (i, t) in self.transformers.iter_mut().enumerate() {
+            let z = match t.uncorrected_impedance(false) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("transformer {} error: {}", i + 1, err));
+                }
+            };
+            let n = t.ratio(TapSelection::Actual);
+            let (v_hv, v_lv) = (v[ix[&t.node_hv]], v[ix[&t.node_lv]]);
+
+            let i_hv = (v_hv / n - v_lv) / z / n; // kA
+            let s_hv = v_hv * i_hv.conj();
+
+            // Power factor angle at the sending end.
+            let s_b = if s_hv.re >= 0.0 {
+                s_hv
+            } else {
+                -v_lv * (i_hv * n).conj()
+            };
+
+            t.ub = Some(v_hv.norm() * SQRT_3);
+            t.ib = i_hv.norm();
+            t.phib = s_b.arg();
+        }
+        Ok(())
+    }
+
+    /// Pre-fault phase voltages (kV) from the operating point, solving the load flow by
+    /// Gauss iteration on the impedance matrix if no voltages are given.
+    fn prefault_voltages<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+        ix: &HashMap<N, usize>,
+        nn: usize,
+        op: &OperatingPoint<N>,
+    ) -> Result<Vec<Complex64>> {
+        if !op.voltages.is_empty() {
+            let mut v: Vec<Complex64> = self
+                .nominal_voltages(ix, nn)
+                .iter()
+                .map(|un| cmplx!(un / SQRT_3))
+                .collect();
+            for (i, u) in op.voltages.iter().enumerate() {
+                let Some(&j) = ix.get(&u.node) else {
+                    return Err(format_err!("voltage {} error: node not found", i + 1));
+                };
+                v[j] = Complex64::from_polar(u.u / SQRT_3, u.angle.to_radians());
+            }
+            return Ok(v);
+        }
+
+        let busbar_index = BusbarIndex::new(&self.busbars);
+
+        let y_mat = self.operating_admittance_matrix(ix, nn, false)?;
+        let factors =
+            solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())?;
+
+        // Source currents of the network feeders (kA).
+        let mut i_src = vec![Complex64::default(); nn];
+        for (i, f) in self.feeders.iter().enumerate() {
+            let Some(busbar) = busbar_index.busbar(&f.node) else {
+                return Err(format_err!("feeder {} error: busbar not found", i + 1));
+            };
+            let eq = cmplx!(op.uq * busbar.un / SQRT_3);
+            i_src[ix[&f.node]] += eq / f.unreferred_impedance(false, &busbar_index)?;
+        }
+
+        // Power injected at each node per phase (MVA).
+        let mut s = vec![Complex64::default(); nn];
+        for (i, inj) in op.injections.iter().enumerate() {
+            let Some(&j) = ix.get(&inj.node) else {
+                return Err(format_err!("injection {} error: node not found", i + 1));
+            };
+            s[j] += cmplx!(inj.p, inj.q) / 3.0;
+        }
+
+        let mut loads = vec![];
+        for (i, l) in self.loads.iter().enumerate() {
+            let Some(busbar) = busbar_index.busbar(&l.node) else {
+                return Err(format_err!("load {} error: busbar not found", i + 1));
+            };
+            loads.push((ix[&l.node], busbar.un, l));
+        }
+
+        let mut v = i_src.clone();
+        solver.solve(&factors, &mut v, false)?;
+
+        for _ in 0..op.max_iter {
+            let mut s = s.clone();
+            for &(j, un, l) in &loads {
+                s[j] -= l.power(v[j].norm() * SQRT_3, un) / 3.0;
+            }
+
+            let mut v_next: Vec<Complex64> = (0..nn)
+                .map(|j| {
+                    if s[j] == Complex64::default() {
+                        i_src[j]
+                    } else {
+                        i_src[j] + (s[j] / v[j]).conj()
+                    }
+                })
+                .collect();
+            solver.solve(&factors, &mut v_next, false)?;
+
+            let dv = v_next
+                .iter()
+                .zip(&v)
+                .map(|(a, b)| (a - b).norm())
+                .fold(0.0, f64::max);
+            v = v_next;
+
+            if dv * SQRT_3 < op.tolerance {
+                return Ok(v);
+            }
+        }
+        Err(format_err!(
+            "load flow did not converge in {} iterations",
+            op.max_iter
+        ))
+    }
+
+    /// Short-circuit currents at the terminals of the generator of a power station unit. The
+    /// partial currents of the generator, of the network fed through the unit transformer and
+    /// of the auxiliary motors are calculated separately and added. The peak short-circuit
+    /// current is calculated from the parallel connection of the partial impedances, with the
+    /// fictitious resistance of the generator, by method B (`ip50`) and by method C (`ip20`).
+    fn station_terminal_fault<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+        i: usize,
+        busbar: &Busbar<N>,
+        tmin: f64,
+    ) -> Result<Fault<N>> {
+        let s = &self.power_stations[i];
+        let g = &s.generator;
+        let busbar_index = BusbarIndex::new(&self.busbars);
+
+        let zq = self.station_network_impedance(solver, i)?;
+        let (zg, zt) = s.terminal_impedances(false, &busbar_index)?;
+        let (zg_p, _) = s.terminal_impedances(true, &busbar_index)?;
+
+        let e = cmplx!(c_or_default(busbar) * busbar.un / SQRT_3);
+        let ikss_g = (e / zg).norm();
+        let ikss_t = (e / (zt + zq)).norm();
+
+        // Admittances of the partial short-circuit currents for the peak short-circuit current,
+        // at the nominal frequency (method B) and the equivalent frequency (method C).
+        let fc_f = 0.4; // 20 Hz for 50 Hz systems, 24 Hz for 60 Hz systems.
+        let fc = |z: Complex64| cmplx!(z.re, z.im * fc_f);
+        let mut y_b = ONE / zg_p + ONE / (zt + zq);
+        let mut y_c = ONE / fc(zg_p) + ONE / fc(zt + zq);
+
+        // D.c. components of the partial currents (64).
+        let f = self.frequency;
+        let mut idc_k = idc(ikss_g, f, tmin, zg.re / zg.im);
+        idc_k += idc(ikss_t, f, tmin, (zt + zq).re / (zt + zq).im);
+
+        let mut fault = Fault {
+            node: busbar.nodes[0].clone(),
+            ikss: ikss_g + ikss_t,
+            ib: g.breaking_current(ikss_g, tmin) + ikss_t,
+            ik: g.steady_state_current(ikss_g) + ikss_t,
+            ..Default::default()
+        };
+
+        // Auxiliary motors fed back through the auxiliary transformers, without decay of the
+        // breaking current.
+        if !s.auxiliary_motors.is_empty() || !s.auxiliary_motor_groups.is_empty() {
+            let (ix, z) = self.auxiliary_impedances(solver, i, None, None, &g.node)?;
+            let zm = z[ix[&g.node]];
+            let ikss_m = (e / zm).norm();
+
+            fault.ikss += ikss_m;
+            fault.ib += ikss_m;
+            idc_k += idc(ikss_m, f, tmin, zm.re / zm.im);
+
+            let (ix, z) = self.auxiliary_impedances(solver, i, None, Some(fc_f), &g.node)?;
+            y_b += ONE / zm;
+            y_c += ONE / z[ix[&g.node]];
+        }
+
+        // Peak short-circuit current.
+        let zk_b = ONE / y_b;
+        let kappa_max = if busbar.un <= 1.0 { 1.8 } else { 2.0 };
+        let kappa_b = (1.15 * kappa(zk_b.re / zk_b.im)).min(kappa_max); // (59)
+        fault.ip50 = kappa_b * 2.0f64.sqrt() * fault.ikss;
+
+        let zk_c = ONE / y_c;
+        let kappa_c = kappa((zk_c.re / zk_c.im) * fc_f); // (60)
+        fault.ip20 = kappa_c * 2.0f64.sqrt() * fault.ikss;
+
+        fault.ib_asym = (fault.ib * fault.ib + idc_k * idc_k).sqrt();
+        fault.idc = idc_k;
+
+        // R/X ratio of the sum of the d.c. components of the partial currents.
+        let omega = 2.0 * std::f64::consts::PI * f;
+        fault.rx = (2.0f64.sqrt() * fault.ikss / idc_k).ln() / (omega * tmin);
+        Ok(fault)
+    }
+
+    /// Short-circuit currents at a busbar of the auxiliary network of a power station unit.
+    /// The auxiliary network is fed at the generator terminals by the generator and by the
+    /// network through the unit transformer, using the correction factors for a short circuit
+    /// between the generator and the unit transformer. The partial currents of the generator
+    /// and the network are taken without decay for the breaking and steady-state currents.
+    fn station_auxiliary_fault<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+        i: usize,
+        busbar: &Busbar<N>,
+        tmin: f64,
+    ) -> Result<Fault<N>> {
+        let s = &self.power_stations[i];
+        let busbar_index = BusbarIndex::new(&self.busbars);
+        let node = &busbar.nodes[0];
+
+        let fc_f = 0.4; // 20 Hz for 50 Hz systems, 24 Hz for 60 Hz systems.
+
+        // Impedance at the generator terminals.
+        let zq = self.station_network_impedance(solver, i)?;
+        let za = |peak: bool| -> Result<Complex64> {
+            let (zg, zt) = s.terminal_impedances(peak, &busbar_index)?;
+            Ok(ONE / (ONE / zg + ONE / (zt + zq)))
+        };
+
+        let (ix, z) = self.auxiliary_impedances(solver, i, Some(za(false)?), None, node)?;
+        let k = ix[node];
+
+        let c = c_or_default(busbar);
+        let ikss = cmplx!(c * busbar.un / SQRT_3) / z[k]; // kA
+
+        let partial = |n: &N, zm: Complex64| {
+            let un = busbar_index.busbar(n).map_or(busbar.un, |b| b.un);
+            let du = z[ix[n]] * ikss; // kV
+            (
+                du.norm() / (c * un / SQRT_3),
+                (du / zm).norm(),
+                un / busbar.un,
+            )
+        };
+
+        // (75)
+        let mut ib = ikss.norm();
+        let mut ik = ikss.norm();
+        for m in &s.auxiliary_motors {
+            let (du, ikss_m, tr) = partial(&m.node, m.impedance()?);
+            ib -= du * (ikss_m - m.breaking_current(ikss_m, tmin)) * tr;
+            ik -= ikss_m * tr;
+        }
+        for m in &s.auxiliary_motor_groups {
+            let (du, ikss_m, tr) = partial(&m.node, m.impedance()?);
+            ib -= du * (ikss_m - m.breaking_current(ikss_m, tmin)) * tr;
+            ik -= ikss_m * tr;
+        }
+
+        // Peak short-circuit current.
+        let za_b = za(true)?;
+        let zk_b = self
+            .auxiliary_impedances(solver, i, Some(za_b), Some(1.0), node)?
+            .1[k];
+        let kappa_max = if busbar.un <= 1.0 { 1.8 } else { 2.0 };
+        let kappa_b = (1.15 * kappa(zk_b.re / zk_b.im)).min(kappa_max); // (59)
+
+        let za_c = cmplx!(za_b.re, za_b.im * fc_f);
+        let zk_c = self
+            .auxiliary_impedances(solver, i, Some(za_c), Some(fc_f), node)?
+            .1[k];
+        let kappa_c = kappa((zk_c.re / zk_c.im) * fc_f); // (60)
+
+        // D.c. component at the minimum time delay.
+        let fc_dc = dc_frequency_ratio(self.frequency, tmin);
+        let za_dc = cmplx!(za_b.re, za_b.im * fc_dc);
+        let zk_dc = self
+            .auxiliary_impedances(solver, i, Some(za_dc), Some(fc_dc), node)?
+            .1[k];
+        let rx_dc = (zk_dc.re / zk_dc.im) * fc_dc;
+        let idc = idc(ikss.norm(), self.frequency, tmin, rx_dc);
+
+        Ok(Fault {
+            node: node.clone(),
+            ikss: ikss.norm(),
+            ip50: kappa_b * 2.0f64.sqrt() * ikss.norm(),
+            ip20: kappa_c * 2.0f64.sqrt() * ikss.norm(),
+            ib,
+            ib_asym: (ib * ib + idc * idc).sqrt(),
+            idc,
+            rx: rx_dc,
+            ik,
+            ..Default::default()
+        })
+    }
+
+    /// Impedance (Ohms) of the network at the high-voltage side of the unit transformer of
+    /// power station unit `i`, without the unit, referred to the low-voltage side.
+    fn station_network_impedance<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+        i: usize,
+    ) -> Result<Complex64> {
+        let s = &self.power_stations[i];
+        let t = &s.transformer;
+
+        let mut unit = s.auxiliary_nodes();
+        unit.push(&s.generator.node);
+
+        let mut net = self.clone();
+        net.busbars
+            .retain(|b| !b.nodes.iter().any(|n| unit.contains(&n)));
+        net.power_stations.remove(i);
+
+        let (ix, nn) = net.nodes();
+        let Some(&k) = ix.get(&t.node_hv) else {
+            return Err(format_err!("power station {} error: not connected", i + 1));
+        };
+        let y_mat = net.admittance_matrix(&ix, nn, Some(&net.all_motors()), None, None)?;
+        let factors =
+            solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())?;
+        let mut z = vec![Complex64::default(); nn];
+        z[k] = ONE;
+        solver.solve(&factors, &mut z, false)?;
+
+        let tr = t.ur_hv / t.ur_lv;
+        Ok(z[k] / (tr * tr))
+    }
+
+    /// Column of the impedance matrix (Ohms) for `node` of the auxiliary network of power
+    /// station unit `i` and the index of its nodes. The transformers are represented at
+    /// their rated ratios and the generator terminals are connected to earth through `za`,
+    /// if given. Reactances are scaled by `peak` for the equivalent frequency.
+    fn auxiliary_impedances<F>(
+        &self,
+        solver: &impl FactorSolver<usize, Complex64, F>,
+        i: usize,
+        za: Option<Complex64>,
+        peak: Option<f64>,
+        node: &N,
+    ) -> Result<(HashMap<N, usize>, Vec<Complex64>)> {
+        let s = &self.power_stations[i];
+        let ib = BusbarIndex::new(&self.busbars);
+
+        let fc = |z: Complex64| match peak {
+            Some(fc_f) => cmplx!(z.re, z.im * fc_f),
+            None => z,
+        };
+
+        let mut ix = HashMap::new();
+        let mut nn = 0;
+        for n in [&s.generator.node].into_iter().chain(s.auxiliary_nodes()) {
+            if ix.contains_key(n) {
+                continue;
+            }
+            match ib.busbar(n) {
+                Some(b) => b.nodes.iter().for_each(|m| {
+                    ix.insert(m.clone(), nn);
+                }),
+                None => {
+                    ix.insert(n.clone(), nn);
+                }
+            }
+            nn += 1;
+        }
+        let star = nn; // Star points of the three-winding transformers.
+        nn += s.auxiliary_three_winding_transformers.len();
+
+        let Some(&k) = ix.get(node) else {
+            return Err(format_err!("power station {} error: node not found", i + 1));
+        };
+
+        let mut y_mat = YBus::new(nn, None);
+
+        if let Some(za) = za {
+            let j = ix[&s.generator.node];
+            y_mat.shunt(j, za)?;
+        }
+
+        for (j, t) in s.auxiliary_transformers.iter().enumerate() {
+            let z = match t.impedance(false, &ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!(
+                        "power station {} auxiliary transformer {} error: {}",
+                        i + 1,
+                        j + 1,
+                        err
+                    ));
+                }
+            };
+            let n = t.ratio(self.taps);
+            y_mat.ratio_branch(ix[&t.node_hv], ix[&t.node_lv], fc(z), n)?;
+        }
+
+        for (j, t) in s.auxiliary_three_winding_transformers.iter().enumerate() {
+            let (z_hv, z_mv, z_lv) = match t.winding_impedances(&ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!(
+                        "power station {} auxiliary transformer {} error: {}",
+                        i + 1,
+                        j + 1,
+                        err
+                    ));
+                }
+            };
+            let (n_mv, n_lv) = t.ratios(self.taps);
+
+            y_mat.branch(ix[&t.node_hv], star + j, fc(z_hv))?;
+            y_mat.ratio_branch(star + j, ix[&t.node_mv], fc(z_mv), n_mv)?;
+            y_mat.ratio_branch(star + j, ix[&t.node_lv], fc(z_lv), n_lv)?;
+        }
+
+        let mut shunts = vec![];
+        for m in &s.auxiliary_motors {
+            shunts.push((&m.node, m.impedance()?));
+        }
+        for m in &s.auxiliary_motor_groups {
+            shunts.push((&m.node, m.impedance()?));
+        }
+        for (n, z) in shunts {
+            let j = ix[n];
+            y_mat.shunt(j, fc(z))?;
+        }
+
+        let y_mat: CSC<usize, Complex64> = y_mat.to_csc();
+        let factors =
+            solver.factor(y_mat.cols(), y_mat.rowidx(), y_mat.colptr(), y_mat.values())?;
+        let mut z = vec![Complex64::default(); nn];
+        z[k] = ONE;
+        solver.solve(&factors, &mut z, false)?;
+
+        Ok((ix, z))
+    }
+
+    /// Elements represented by current sources.
+    fn current_sources(&self) -> impl Iterator<Item = &dyn CurrentSource<N>> {
+        self.full_converter_wind_units
+            .iter()
+            .map(|w| w as &dyn CurrentSource<N>)
+            .chain(
+                self.full_converter_units
+                    .iter()
+                    .map(|u| u as &dyn CurrentSource<N>),
+            )
+    }
+
+    fn admittance_matrix(
+        &self,
+        ix: &HashMap<N, usize>,
+        nn: usize,
+        motors: Option<&MotorSelection>,
+        peak: Option<f64>,
+        base: Option<&PerUnit>,
+    ) -> Result<CSC<usize, Complex64>> {
+        let ib = BusbarIndex::new(&self.busbars);
+
+        // Reactances at the equivalent frequency for the peak short-circuit current.
+        let fc = |z: Complex64| match peak {
+            Some(fc_f) => cmplx!(z.re, z.im * fc_f),
+            None => z,
+        };
+
+        let zbase = base.map(|b| {
+            self.nominal_voltages(ix, nn)
+                .iter()
+                .map(|&un| b.zbase(un))
+                .collect()
+        });
+        let mut y_mat = YBus::new(nn, zbase);
+
+        for (i, f) in self.feeders.iter().enumerate() {
+            let z = match f.unreferred_impedance(false, &ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("feeder {} error: {}", i + 1, err));
+                }
+            };
+            let z = fc(z);
+            if z == Complex64::default() {
+                return Err(format_err!("feeder {} error: zero impedance", i + 1));
+            }
+            let j: usize = ix[&f.node];
+            y_mat.shunt(j, z)?;
+        }
+
+        for (i, t) in self.transformers.iter().enumerate() {
+            let z = match t.impedance(false, &ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("transformer {} error: {}", i + 1, err));
+                }
+            };
+            let z = fc(z);
+            if z == Complex64::default() {
+                return Err(format_err!("transformer {} error: zero impedance", i + 1));
+            }
+            let n = t.ratio(self.taps);
+            y_mat.ratio_branch(ix[&t.node_hv], ix[&t.node_lv], z, n)?;
+        }
+
+        for (i, t) in self.three_winding_transformers.iter().enumerate() {
+            let (z_hv, z_mv, z_lv) = match t.winding_impedances(&ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!(
+                        "three-winding transformer {} error: {}",
+                        i + 1,
+                        err
+                    ));
+                }
+            };
+            let star = self.star(nn, i);
+            let (n_mv, n_lv) = t.ratios(self.taps);
+
+            y_mat.branch(ix[&t.node_hv], star, fc(z_hv))?;
+            y_mat.ratio_branch(star, ix[&t.node_mv], fc(z_mv), n_mv)?;
+            y_mat.ratio_branch(star, ix[&t.node_lv], fc(z_lv), n_lv)?;
+        }
+
+        for (i, g) in self.generators.iter().enumerate() {
+            let z = match g.impedance(GENERATOR_VOLTAGE_TOLERANCE, peak.is_some(), &ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("generator {} error: {}", i + 1, err));
+                }
+            };
+            let z = fc(z);
+            let j = ix[&g.node];
+            y_mat.shunt(j, z)?;
+        }
+
+        for (i, s) in self.power_stations.iter().enumerate() {
+            if s.generator.node != s.transformer.node_lv {
+                return Err(format_err!(
+                    "power station {} error: generator not connected to transformer",
+                    i + 1
+                ));
+            }
+            let (zg, zt) = match s.impedances(false, peak.is_some(), &ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("power station {} error: {}", i + 1, err));
+                }
+            };
+            let (zg, zt) = (fc(zg), fc(zt));
+            let t = &s.transformer;
+            let k = ix[&t.node_lv];
+
+            y_mat.shunt(k, zg)?;
+            y_mat.ratio_branch(ix[&t.node_hv], k, zt, t.ratio(self.taps))?;
+        }
+
+        for (i, m) in self.synchronous_motors.iter().enumerate() {
+            let z = match m.impedance(peak.is_some(), &ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("synchronous motor {} error: {}", i + 1, err));
+                }
+            };
+            let z = fc(z);
+            let j = ix[&m.node];
+            y_mat.shunt(j, z)?;
+        }
+
+        for (i, w) in self.doubly_fed_wind_units.iter().enumerate() {
+            let z = match w.impedance() {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!(
+                        "wind power station unit {} error: {}",
+                        i + 1,
+                        err
+                    ));
+                }
+            };
+            let z = fc(z);
+            let j = ix[&w.node];
+            y_mat.shunt(j, z)?;
+        }
+
+        for (i, c) in self.cables.iter().enumerate() {
+            let z = match c.unreferred_impedance() {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("cable {} error: {}", i + 1, err));
+                }
+            };
+            let z = fc(z);
+            if z == Complex64::default() {
+                return Err(format_err!("cable {} error: zero impedance", i + 1));
+            }
+            y_mat.branch(ix[&c.node_i], ix[&c.node_j], z)?;
+        }
+
+        for (i, l) in self.lines.iter().enumerate() {
+            let z = match l.impedance(self.frequency) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("line {} error: {}", i + 1, err));
+                }
+            };
+            let z = fc(z);
+            if z == Complex64::default() {
+                return Err(format_err!("line {}: zero impedance", i + 1));
+            }
+            y_mat.branch(ix[&l.node_i], ix[&l.node_j], z)?;
+        }
+
+        for c in &self.series_capacitors {
+            let z = if c.neglected() {
+                cmplx!(BYPASS_IMPEDANCE)
+            } else {
+                match peak {
+                    Some(fc_f) => c.impedance() / fc_f,
+                    None => c.impedance(),
+                }
+            };
+            y_mat.branch(ix[&c.node_i], ix[&c.node_j], z)?;
+        }
+
+        if let Some(selection) = motors {
+            for (i, m) in self.motors.iter().enumerate() {
+                if !selection.motors[i] {
+                    continue;
+                }
+                let z = match m.impedance() {
+                    Ok(z) => z,
+                    Err(err) => {
+                        return Err(format_err!("motor {} error: {}", i + 1, err));
+                    }
+                };
+                let z = fc(z);
+                if z == Complex64::default() {
+                    return Err(format_err!("motor {} error: zero impedance", i + 1));
+                }
+                let j = ix[&m.node];
+                y_mat.shunt(j, z)?;
+            }
+            for (i, m) in self.motor_groups.iter().enumerate() {
+                if !selection.motor_groups[i] {
+                    continue;
+                }
+                let z = match m.impedance() {
+                    Ok(z) => z,
+                    Err(err) => {
+                        return Err(format_err!("motor group {} error: {}", i + 1, err));
+                    }
+                };
+                let z = fc(z);
+                let j = ix[&m.node];
+                y_mat.shunt(j, z)?;
+            }
+            for (i, d) in self.converter_drives.iter().enumerate() {
+                let z = match d.impedance() {
+                    Ok(z) => z,
+                    Err(err) => {
+                        return Err(format_err!("converter drive {} error: {}", i + 1, err));
+                    }
+                };
+                let z = fc(z);
+                let j = ix[&d.node];
+                y_mat.shunt(j, z)?;
+            }
+        }
+
+        for (i, s) in self.current_sources().enumerate() {
+            if y_mat.isolated(ix[s.node()]) {
+                return Err(format_err!("current source {} error: not connected", i + 1));
+            }
+        }
+
+        Ok(y_mat.to_csc())
+    }
+
+    /// Admittance matrix of the zero-sequence system. Transformers are included according to
+    /// the connection of their windings and coupled lines are added as coupled branches.
+    fn zero_sequence_admittance_matrix(
+        &self,
+        ix: &HashMap<N, usize>,
+        nn: usize,
+    ) -> Result<CSC<usize, Complex64>> {
+        let ib = BusbarIndex::new(&self.busbars);
+
+        let mut y_mat = YBus::new(nn, None);
+        for j in 0..nn {
+            y_mat.shunt(j, cmplx!(1.0 / ISOLATED_ADMITTANCE))?;
+        }
+
+        for (i, f) in self.feeders.iter().enumerate() {
+            if f.x0x == 0.0 {
+                continue;
+            }
+            let z = match f.zero_sequence_impedance(&ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("feeder {} error: {}", i + 1, err));
+                }
+            };
+            y_mat.shunt(ix[&f.node], z)?;
+        }
+
+        let mut transformers = vec![];
+        for (i, t) in self.transformers.iter().enumerate() {
+            if t.zero_sequence == ZeroSequenceConnection::Open {
+                continue;
+            }
+            let z = match t.zero_sequence_impedance(false, &ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("transformer {} error: {}", i + 1, err));
+                }
+            };
+            if z == Complex64::default() {
+                return Err(format_err!("transformer {} error: zero impedance", i + 1));
+            }
+            transformers.push((t, z));
+        }
+        for (i, s) in self.power_stations.iter().enumerate() {
+            let t = &s.transformer;
+            if t.zero_sequence == ZeroSequenceConnection::Open {
+                continue;
+            }
+            let (_, zt) = match s.impedances(false, false, &ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("power station {} error: {}", i + 1, err));
+                }
+            };
+            let z = cmplx!(zt.re * t.r0r, zt.im * t.x0x);
+            if z == Complex64::default() {
+                return Err(format_err!("power station {} error: zero impedance", i + 1));
+            }
+            transformers.push((t, z));
+        }
+        for (t, z) in transformers {
+            let (j, k) = (ix[&t.node_hv], ix[&t.node_lv]);
+            let n = t.ratio(self.taps);
+            match t.zero_sequence {
+                ZeroSequenceConnection::Open => {}
+                ZeroSequenceConnection::Hv => y_mat.shunt(j, z * n * n)?,
+                ZeroSequenceConnection::Lv => y_mat.shunt(k, z)?,
+                ZeroSequenceConnection::Both => y_mat.ratio_branch(j, k, z, n)?,
+            }
+        }
+
+        for (i, c) in self.cables.iter().enumerate() {
+            let z = c.zero_sequence_impedance()?;
+            if z == Complex64::default() {
+                return Err(format_err!("cable {} error: zero impedance", i + 1));
+            }
+            y_mat.branch(ix[&c.node_i], ix[&c.node_j], z)?;
+        }
+
+        let mut z0 = vec![];
+        for (i, l) in self.lines.iter().enumerate() {
+            let z = match l.zero_sequence_impedance(self.frequency) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("line {} error: {}", i + 1, err));
+                }
+            };
+            if z == Complex64::default() {
+                return Err(format_err!("line {}: zero impedance", i + 1));
+            }
+            z0.push(z);
+        }
+
+        let mut coupled = vec![false; self.lines.len()];
+        for (group, z) in self.coupling_groups(&z0)? {
+            let mut branches = vec![];
+            for &i in &group {
+                let l = &self.lines[i];
+                branches.push((ix[&l.node_i], ix[&l.node_j]));
+                coupled[i] = true;
+            }
+            y_mat.coupled_branches(&branches, &z)?;
+        }
+        for (i, l) in self.lines.iter().enumerate() {
+            if !coupled[i] {
+                y_mat.branch(ix[&l.node_i], ix[&l.node_j], z0[i])?;
+            }
+        }
+        for c in &self.series_capacitors {
+            let z = if c.neglected() {
+                cmplx!(BYPASS_IMPEDANCE)
+            } else {
+                c.impedance()
+            };
+            y_mat.branch(ix[&c.node_i], ix[&c.node_j], z)?;
+        }
+
+        Ok(y_mat.to_csc())
+    }
+
+    /// Admittance matrix of the network with the impedances of the elements without
+    /// correction factors and the transformers at their rated ratio. Machines are included
+    /// if `machines` is true.
+    fn operating_admittance_matrix(
+        &self,
+        ix: &HashMap<N, usize>,
+        nn: usize,
+        machines: bool,
+    ) -> Result<CSC<usize, Complex64>> {
+        let ib = BusbarIndex::new(&self.busbars);
+
+        let mut y_mat = YBus::new(nn, None);
+
+        for (i, f) in self.feeders.iter().enumerate() {
+            let z = match f.unreferred_impedance(false, &ib) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("feeder {} error: {}", i + 1, err));
+                }
+            };
+            let j = ix[&f.node];
+            y_mat.shunt(j, z)?;
+        }
+
+        for (i, t) in self.transformers.iter().enumerate() {
+            let z = match t.uncorrected_impedance(false) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("transformer {} error: {}", i + 1, err));
+                }
+            };
+            let n = t.ratio(TapSelection::Actual);
+            y_mat.ratio_branch(ix[&t.node_hv], ix[&t.node_lv], z, n)?;
+        }
+
+        for (i, t) in self.three_winding_transformers.iter().enumerate() {
+            let (z_hv, z_mv, z_lv) = match t.uncorrected_winding_impedances() {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!(
+                        "three-winding transformer {} error: {}",
+                        i + 1,
+                        err
+                    ));
+                }
+            };
+            let star = self.star(nn, i);
+            let (n_mv, n_lv) = t.ratios(TapSelection::Actual);
+
+            y_mat.branch(ix[&t.node_hv], star, z_hv)?;
+            y_mat.ratio_branch(star, ix[&t.node_mv], z_mv, n_mv)?;
+            y_mat.ratio_branch(star, ix[&t.node_lv], z_lv, n_lv)?;
+        }
+
+        for (i, s) in self.power_stations.iter().enumerate() {
+            let t = &s.transformer;
+            let z = match t.uncorrected_impedance(false) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("power station {} error: {}", i + 1, err));
+                }
+            };
+            let n = t.ratio(TapSelection::Actual);
+            y_mat.ratio_branch(ix[&t.node_hv], ix[&t.node_lv], z, n)?;
+
+            if machines {
+                let zg = s.generator.uncorrected_impedance();
+                let j = ix[&t.node_lv];
+                y_mat.shunt(j, zg)?;
+            }
+        }
+
+        for (i, c) in self.cables.iter().enumerate() {
+            let z = match c.unreferred_impedance() {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("cable {} error: {}", i + 1, err));
+                }
+            };
+            y_mat.branch(ix[&c.node_i], ix[&c.node_j], z)?;
+        }
+
+        for (i, l) in self.lines.iter().enumerate() {
+            let z = match l.impedance(self.frequency) {
+                Ok(z) => z,
+                Err(err) => {
+                    return Err(format_err!("line {} error: {}", i + 1, err));
+                }
+            };
+            y_mat.branch(ix[&l.node_i], ix[&l.node_j], z)?;
+        }
+
+        for c in &self.series_capacitors {
+            let z = if c.bypass {
+                cmplx!(BYPASS_IMPEDANCE)
+            } else {
+                c.impedance()
+            };
+            y_mat.branch(ix[&c.node_i], ix[&c.node_j], z)?;
+        }
+
+        if machines {
+            let mut shunts = vec![];
+            for g in &self.generators {
+                shunts.push((&g.node, g.uncorrected_impedance()));
+            }
+            for m in &self.synchronous_motors {
+                shunts.push((&m.node, m.uncorrected_impedance()));
+            }
+            for m in &self.motors {
+                shunts.push((&m.node, m.impedance()?));
+            }
+            for m in &self.motor_groups {
+                shunts.push((&m.node, m.impedance()?));
+            }
+            for d in &self.converter_drives {
+                shunts.push((&d.node, d.impedance()?));
+            }
+            for w in &self.doubly_fed_wind_units {
+                shunts.push((&w.node, w.impedance()?));
+            }
+            for (n, z) in shunts {
+                let j = ix[n];
+                y_mat.shunt(j, z)?;
+            }
+        }
+
+        Ok(y_mat.to_csc())
+    }
+
+    /// Groups of mutually coupled overhead lines with the zero-sequence impedance matrix
+    /// (Ohms) of each group, given the zero-sequence impedances `z0` of the lines. Lines
+    /// coupled directly or through a common line form a group, such as the circuits on one
+    /// tower. Lines of a group without a coupling between them have no mutual impedance.
+    fn coupling_groups(&self, z0: &[Complex64]) -> Result<Vec<CouplingGroup>> {
+        let nl = self.lines.len();
+
+        let mut group: Vec<usize> = (0..nl).collect();
+        let mut zm = HashMap::new();
+        for (i, m) in self.couplings.iter().enumerate() {
+            let (a, b) = (m.line_i.min(m.line_j), m.line_i.max(m.line_j));
+            if a == b || b >= nl {
+                return Err(format_err!("coupling {} error: invalid lines", i + 1));
+            }
+            let l = self.lines[a].l.min(self.lines[b].l);
+            if zm.insert((a, b), m.impedance(l)).is_some() {
+                return Err(format_err!(
+                    "coupling {} error: lines already coupled",
+                    i + 1
+                ));
+            }
+            let (ga, gb) = (group[a], group[b]);
+            group.iter_mut().filter(|g| **g == gb).for_each(|g| *g = ga);
+        }
+
+        let mut groups = vec![];
+        for g in 0..nl {
+            let lines: Vec<usize> = (0..nl).filter(|&i| group[i] == g).collect();
+            if lines.len() < 2 {
+                continue;
+            }
+            let z = lines
+                .iter()
+                .map(|&p| {
+                    lines
+                        .iter()
+                        .map(|&q| match p.cmp(&q) {
+                            std::cmp::Ordering::Equal => z0[p],
+                            std::cmp::Ordering::Less => {
+                                zm.get(&(p, q)).copied().unwrap_or_default()
+                            }
+                            std::cmp::Ordering::Greater => {
+                                zm.get(&(q, p)).copied().unwrap_or_default()
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+            groups.push((lines, z));
+        }
+        Ok(groups)
+    }
+
+    /// Returns the index of the galvanically connected part of the network (voltage level)
+    /// of each node. Busbars, lines, cables and series capacitors connect nodes, transformers
+    /// separate them.
+    fn islands(&self, ix: &HashMap<N, usize>, nn: usize) -> Vec<usize> {
+        let mut parent: Vec<usize> = (0..nn).collect();
+
+        fn find(parent: &mut [usize], i: usize) -> usize {
+            let mut r = i;
+            while parent[r] != r {
+                r = parent[r];
+            }
+            parent[i] = r;
+            r
+        }
+
+        let mut join = |i: &N, j: &N| {
+            let a = find(&mut parent, ix[i]);
+            let b = find(&mut parent, ix[j]);
+            parent[a.max(b)] = a.min(b);
+        };
+        for l in &self.lines {
+            join(&l.node_i, &l.node_j);
+        }
+        for c in &self.cables {
+            join(&c.node_i, &c.node_j);
+        }
+        for c in &self.series_capacitors {
+            join(&c.node_i, &c.node_j);
+        }
+
+        (0..nn).map(|i| find(&mut parent, i)).collect()
+    }
+
+    fn nodes(&self) -> (HashMap<N, usize>, usize) {
+        let mut nodes = HashMap::new();
+        let mut n = 0;
+
+        // Auxiliary networks of power station units are calculated separately.
+        let auxiliary: Vec<&N> = self
+            .power_stations
+            .iter()
+            .flat_map(|s| s.auxiliary_nodes())
+            .collect();
+
+        for b in &self.busbars {
+            if b.nodes.iter().any(|t| auxiliary.contains(&t)) {
+                continue;
+            }
+            for t in &b.nodes {
+                nodes.insert(t.clone(), n);
+            }
+            n += 1;
+        }
+
+        let mut add = |t: &N| {
+            if !nodes.contains_key(t) {
+                nodes.insert(t.clone(), n);
+                n += 1;
+            }
+        };
+
+        for f in &self.feeders {
+            add(&f.node);
+        }
+        for s in &self.power_stations {
+            // if s.Generator != nil {
+            add(&s.generator.node);
+            // }
+            // if s.Transformer != nil {
+            add(&s.transformer.node_hv);
+            add(&s.transformer.node_lv);
+            // }
+        }
+        for g in &self.generators {
+            add(&g.node);
+        }
+        for t in &self.transformers {
+            add(&t.node_hv);
+            add(&t.node_lv);
+        }
+        for t in &self.three_winding_transformers {
+            add(&t.node_hv);
+            add(&t.node_mv);
+            add(&t.node_lv);
+        }
+        for m in &self.motors {
+            add(&m.node);
+        }
+        for m in &self.motor_groups {
+            add(&m.node);
+        }
+        for d in &self.converter_drives {
+            add(&d.node);
+        }
+        for m in &self.synchronous_motors {
+            add(&m.node);
+        }
+        for w in &self.doubly_fed_wind_units {
+            add(&w.node);
+        }
+        for s in self.current_sources() {
+            add(s.node());
+        }
+        for l in &self.lines {
+            add(&l.node_i);
+            add(&l.node_j);
+        }
+        for c in &self.cables {
+            add(&c.node_i);
+            add(&c.node_j);
+        }
+        for c in &self.series_capacitors {
             add(&c.node_i);
             add(&c.node_j);
         }
+        // Star points of the three-winding transformers.
+        n += self.three_winding_transformers.len();
+
         (nodes, n)
     }
+
+    /// Selection of all asynchronous motors and low-voltage motor groups.
+    fn all_motors(&self) -> MotorSelection {
+        MotorSelection {
+            motors: vec![true; self.motors.len()],
+            motor_groups: vec![true; self.motor_groups.len()],
+        }
+    }
+
+    /// Index of the star point of three-winding transformer `i`.
+    fn star(&self, nn: usize, i: usize) -> usize {
+        nn - self.three_winding_transformers.len() + i
+    }
+
+    /// Nominal system voltage (kV) of each node. Nodes outside the busbars take the voltage
+    /// level of the busbars they are connected to, using the rated transformation ratios of
+    /// the transformers, or the rated voltage of the connected elements.
+    fn nominal_voltages(&self, ix: &HashMap<N, usize>, nn: usize) -> Vec<f64> {
+        let mut un = vec![0.0; nn];
+        for b in &self.busbars {
+            for n in &b.nodes {
+                if let Some(&j) = ix.get(n) {
+                    un[j] = b.un;
+                }
+            }
+        }
+
+        // Branches from node j to node k with ratio Un,j / Un,k.
+        let mut branches = vec![];
+        for l in &self.lines {
+            branches.push((ix[&l.node_i], ix[&l.node_j], 1.0));
+        }
+        for c in &self.cables {
+            branches.push((ix[&c.node_i], ix[&c.node_j], 1.0));
+        }
+        for c in &self.series_capacitors {
+            branches.push((ix[&c.node_i], ix[&c.node_j], 1.0));
+        }
+        for t in &self.transformers {
+            branches.push((ix[&t.node_hv], ix[&t.node_lv], t.ur_hv / t.ur_lv));
+        }
+        for s in &self.power_stations {
+            let t = &s.transformer;
+            branches.push((ix[&t.node_hv], ix[&t.node_lv], t.ur_hv / t.ur_lv));
+        }
+        for (i, t) in self.three_winding_transformers.iter().enumerate() {
+            let star = self.star(nn, i);
+            branches.push((ix[&t.node_hv], star, 1.0));
+            branches.push((star, ix[&t.node_mv], t.ur_hv / t.ur_mv));
+            branches.push((star, ix[&t.node_lv], t.ur_hv / t.ur_lv));
+        }
+
+        let propagate = |un: &mut Vec<f64>| loop {
+            let mut changed = false;
+            for &(j, k, n) in &branches {
+                if un[k] == 0.0 && un[j] != 0.0 {
+                    un[k] = un[j] / n;
+                    changed = true;
+                } else if un[j] == 0.0 && un[k] != 0.0 {
+                    un[j] = un[k] * n;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        };
+        propagate(&mut un);
+
+        // Parts of the network without busbars.
+        let mut rated = vec![];
+        for f in &self.feeders {
+            rated.push((&f.node, f.ur));
+        }
+        for t in &self.transformers {
+            rated.extend([(&t.node_hv, t.ur_hv), (&t.node_lv, t.ur_lv)]);
+        }
+        for s in &self.power_stations {
+            rated.push((&s.generator.node, s.generator.ur));
+        }
+        for g in &self.generators {
+            rated.push((&g.node, g.ur));
+        }
+        for m in &self.synchronous_motors {
+            rated.push((&m.node, m.ur));
+        }
+        for m in &self.motors {
+            rated.push((&m.node, m.ur));
+        }
+        for (n, ur) in rated {
+            let j = ix[n];
+            if un[j] == 0.0 {
+                un[j] = ur;
+                propagate(&mut un);
+            }
+        }
+        un
+    }
+}
+
+/// Asynchronous motors and low-voltage motor groups included in the admittance matrix.
+#[derive(Clone, PartialEq, Debug)]
+struct MotorSelection {
+    motors: Vec<bool>,
+    motor_groups: Vec<bool>,
+}
+
+/// Admittance matrix under assembly. If base impedances (Ohms) are given for the nodes, the
+/// impedances of the elements are converted to p.u. as they are added.
+struct YBus {
+    y_mat: DoK<usize, Complex64>,
+    zbase: Option<Vec<f64>>,
+}
+
+impl YBus {
+    fn new(nn: usize, zbase: Option<Vec<f64>>) -> Self {
+        YBus {
+            y_mat: DoK::new(nn, nn),
+            zbase,
+        }
+    }
+
+    /// Adds a shunt with impedance `z` at node `j`.
+    fn shunt(&mut self, j: usize, z: Complex64) -> Result<()> {
+        let z = match &self.zbase {
+            Some(zbase) => z / zbase[j],
+            None => z,
+        };
+        self.y_mat.add(j, j, ONE / z)?;
+        Ok(())
+    }
+
+    /// Adds a branch with impedance `z` between nodes `j` and `k`.
+    fn branch(&mut self, j: usize, k: usize, z: Complex64) -> Result<()> {
+        self.ratio_branch(j, k, z, 1.0)
+    }
+
+    /// Adds a transformer from node `j` to node `k` with impedance `z` on the side of node
+    /// `k` and ratio `n`. In p.u. the ratio is related to the ratio of the base voltages.
+    fn ratio_branch(&mut self, j: usize, k: usize, z: Complex64, n: f64) -> Result<()> {
+        let (z, n) = match &self.zbase {
+            Some(zbase) => (z / zbase[k], n * (zbase[k] / zbase[j]).sqrt()),
+            None => (z, n),
+        };
+        let y = ONE / z;
+        self.y_mat.add(j, j, y / (n * n))?;
+        self.y_mat.sub(j, k, y / n)?;
+        self.y_mat.sub(k, j, y / n)?;
+        self.y_mat.add(k, k, y)?;
+        Ok(())
+    }
+
+    /// Adds mutually coupled branches, each from node `j` to node `k`, with the impedance
+    /// matrix `z` of the branches.
+    fn coupled_branches(
+        &mut self,
+        branches: &[(usize, usize)],
+        z: &[Vec<Complex64>],
+    ) -> Result<()> {
+        let zbase = |j: usize| self.zbase.as_ref().map_or(1.0, |zbase| zbase[j]);
+        let z: Vec<Vec<Complex64>> = branches
+            .iter()
+            .zip(z)
+            .map(|(b, row)| row.iter().map(|&z| z / zbase(b.1)).collect())
+            .collect();
+
+        // Inverse of the branch impedance matrix.
+        let y = invert(&z)?;
+
+        for (p, yp) in branches.iter().zip(&y) {
+            for (q, &y) in branches.iter().zip(yp) {
+                self.y_mat.add(p.0, q.0, y)?;
+                self.y_mat.sub(p.0, q.1, y)?;
+                self.y_mat.sub(p.1, q.0, y)?;
+                self.y_mat.add(p.1, q.1, y)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if no element is connected to node `j`.
+    fn isolated(&self, j: usize) -> bool {
+        self.y_mat.get(j, j) == Complex64::default()
+    }
+
+    fn to_csc(&self) -> CSC<usize, Complex64> {
+        self.y_mat.to_csc()
+    }
 }
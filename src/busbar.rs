@@ -80,13 +80,13 @@ pub(crate) fn voltage_correction_factor(un: f64, min: bool, six_percent: bool) -
         }
     } else {
         match un {
+            _ if un <= 1.0 && six_percent => {
+                // Low voltage
+                1.05
+            }
             _ if un <= 1.0 => {
                 // Low voltage
-                if six_percent {
-                    1.05
-                } else {
-                    1.10
-                }
+                1.10
             }
             _ if un > 1.0 && un <= 35.0 => {
                 // Medium voltage
@@ -112,11 +112,8 @@ macro_rules! busbar {
     ($un:expr, $( $args:expr ),*) => {
         {
             let un = f64::from($un);
-            let mut nodes = vec![];
-            $(
-                nodes.push($args);
-            )*
-            crate::busbar::Busbar{
+            let nodes = vec![$($args),*];
+            $crate::busbar::Busbar{
                 un,
                 // node: None,
                 nodes,
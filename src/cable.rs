@@ -1,8 +1,9 @@
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use derive_builder::Builder;
 use num_complex::Complex64;
 use serde::{Deserialize, Serialize};
 
+use crate::earth::CableSheath;
 use crate::{cmplx, traits::Sq};
 
 #[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
@@ -31,12 +32,74 @@ pub struct Cable<N: Default> {
     /// Zero-sequence short-circuit reactance (Ohms/km).
     pub x0: f64,
 
+    /// Positive-sequence capacitance (uF/km).
+    pub c1: f64,
+
+    /// Zero-sequence capacitance, the capacitance of each conductor to earth (uF/km).
+    pub c0: f64,
+
     /// Number of parallel cables.
     #[builder(setter(into = false), default = "1")]
     pub parallel: usize,
 
     /// Rated transformation ratio at which the on-load tap-changer is in the main position (>= 1).
+    /// Only used to refer the impedance by hand, [`ACSystem`](crate::ACSystem) refers each
+    /// element to the voltage level of its nodes.
     pub tr: Option<f64>,
+
+    /// Metallic sheath, earthed at both ends.
+    pub sheath: Option<CableSheath>,
+
+    /// Cross-section of a conductor (mm^2).
+    pub qn: f64,
+
+    /// Conductor material.
+    pub material: ConductorMaterial,
+
+    /// Insulation material.
+    pub insulation: Insulation,
+}
+
+/// Conductor material of a cable.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum ConductorMaterial {
+    #[default]
+    Copper,
+
+    Aluminium,
+}
+
+/// Insulation material of a cable.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum Insulation {
+    /// Polyvinyl chloride, 70 °C to 160 °C, or to 140 °C for cross-sections above 300 mm^2.
+    #[default]
+    Pvc,
+
+    /// Cross-linked polyethylene, 90 °C to 250 °C.
+    Xlpe,
+}
+
+/// Adiabatic short-circuit withstand of the conductors of a cable, I^2 t <= k^2 S^2.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct CableThermalCheck {
+    /// Thermal equivalent short-circuit current through each of the parallel cables (kA).
+    pub ith: f64,
+
+    /// Duration of the short-circuit current (s).
+    pub tk: f64,
+
+    /// Factor k of the conductor and insulation materials (A s^1/2 / mm^2).
+    pub k: f64,
+
+    /// Cross-section of a conductor (mm^2).
+    pub qn: f64,
+
+    /// Minimum admissible cross-section S = Ith sqrt(Tk) / k (mm^2).
+    pub s_min: f64,
+
+    /// The cross-section withstands the short-circuit current.
+    pub pass: bool,
 }
 
 impl<N: Clone + Default> Cable<N> {
@@ -44,21 +107,88 @@ impl<N: Clone + Default> Cable<N> {
         CableBuilder::default()
     }
 
+    /// Impedance (Ohms) referred to the low-voltage side of a transformer with ratio `tr`,
+    /// if given, or at the voltage level of the cable otherwise.
     pub fn impedance(&self) -> Result<Complex64> {
+        let zl = self.unreferred_impedance()?;
+
+        Ok(match self.tr {
+            Some(tr) => zl / tr.sq(),
+            None => zl,
+        })
+    }
+
+    /// Impedance (Ohms) at the voltage level of the cable.
+    pub fn unreferred_impedance(&self) -> Result<Complex64> {
         let n = self.parallel as f64;
         let rl = self.rl;
         let xl = self.xl;
         let l = self.l;
 
-        let mut r = (1.0 / n) * rl * l;
-        let mut x = (1.0 / n) * xl * l;
-        if let Some(tr) = self.tr {
-            r *= 1.0 / tr.sq();
-            x *= 1.0 / tr.sq();
-        }
+        let r = (1.0 / n) * rl * l;
+        let x = (1.0 / n) * xl * l;
 
         let zl = cmplx!(r, x);
 
         Ok(zl)
     }
+
+    /// Reduction factor of the sheath, the part of the zero-sequence current returning
+    /// through earth. Without sheath the factor is 1.
+    pub fn reduction_factor(&self, f: f64) -> Complex64 {
+        match &self.sheath {
+            Some(sheath) => sheath.reduction_factor(f),
+            None => cmplx!(1),
+        }
+    }
+
+    /// Zero-sequence impedance (Ohms) at the voltage level of the cable.
+    pub fn zero_sequence_impedance(&self) -> Result<Complex64> {
+        let n = self.parallel as f64;
+
+        Ok(cmplx!(self.r0, self.x0) * cmplx!(self.l / n))
+    }
+
+    /// Positive and zero-sequence capacitances (uF) of the parallel cables.
+    pub fn capacitances(&self) -> (f64, f64) {
+        let n = self.parallel as f64;
+
+        (self.c1 * self.l * n, self.c0 * self.l * n)
+    }
+
+    /// Factor k (A s^1/2 / mm^2) of the conductor and insulation materials for the
+    /// adiabatic temperature rise from the maximum operating temperature to the maximum
+    /// short-circuit temperature (IEC 60949). The lower short-circuit temperature of PVC
+    /// insulation above 300 mm^2 reduces the factor.
+    pub fn k(&self) -> f64 {
+        let large = self.qn > 300.0;
+        match (self.material, self.insulation) {
+            (ConductorMaterial::Copper, Insulation::Pvc) if large => 103.0,
+            (ConductorMaterial::Copper, Insulation::Pvc) => 115.0,
+            (ConductorMaterial::Copper, Insulation::Xlpe) => 143.0,
+            (ConductorMaterial::Aluminium, Insulation::Pvc) if large => 68.0,
+            (ConductorMaterial::Aluminium, Insulation::Pvc) => 76.0,
+            (ConductorMaterial::Aluminium, Insulation::Xlpe) => 94.0,
+        }
+    }
+
+    /// Checks the conductors for the thermal equivalent short-circuit current `ith` (kA)
+    /// through the parallel cables and the duration of the short-circuit current `tk` (s).
+    pub fn thermal_check(&self, ith: f64, tk: f64) -> Result<CableThermalCheck> {
+        if self.qn <= 0.0 {
+            return Err(format_err!("cross-section not given"));
+        }
+        let ith = ith / self.parallel as f64;
+        let k = self.k();
+        let s_min = ith * 1e3 * tk.sqrt() / k;
+
+        Ok(CableThermalCheck {
+            ith,
+            tk,
+            k,
+            qn: self.qn,
+            s_min,
+            pass: self.qn >= s_min,
+        })
+    }
 }
@@ -0,0 +1,41 @@
+use derive_builder::Builder;
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+use crate::cmplx;
+
+/// Series capacitor for the compensation of a line, protected by a metal-oxide varistor
+/// (MOV) or a bypass switch in parallel.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into, strip_option))]
+pub struct SeriesCapacitor<N: Default> {
+    pub node_i: N,
+
+    pub node_j: N,
+
+    /// Reactance of the capacitor at the system frequency (Ohms).
+    pub xc: f64,
+
+    /// Bypass switch closed, the capacitor is out of service.
+    pub bypass: bool,
+
+    /// Protective level of the metal-oxide varistor (kV, peak).
+    pub upl: Option<f64>,
+}
+
+impl<N: Clone + Default> SeriesCapacitor<N> {
+    pub fn new() -> SeriesCapacitorBuilder<N> {
+        SeriesCapacitorBuilder::default()
+    }
+
+    /// Impedance of the capacitor (Ohms).
+    pub fn impedance(&self) -> Complex64 {
+        cmplx!(0, -self.xc)
+    }
+
+    /// Series capacitors with voltage-limiting devices in parallel, which act when a short
+    /// circuit occurs, are neglected in the calculation of short-circuit currents.
+    pub fn neglected(&self) -> bool {
+        self.bypass || self.upl.is_some()
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use crate::math::SQRT_3;
+
+/// Group of motors connected to the same part of the network and the outcome of the
+/// IEC 60909-0 criteria for neglecting their contribution to a short circuit.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct MotorContribution {
+    /// Indexes of the asynchronous motors in the group.
+    pub motors: Vec<usize>,
+
+    /// Indexes of the low-voltage motor groups in the group.
+    pub motor_groups: Vec<usize>,
+
+    /// Is the contribution of the group included in the short-circuit current?
+    pub included: bool,
+
+    /// Criterion used to decide if the group is included.
+    pub criterion: MotorCriterion,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum MotorCriterion {
+    /// Motors connected directly to the short-circuited network may be neglected if the sum
+    /// of their rated currents (kA) is below 1% of the initial symmetrical short-circuit
+    /// current without motors (kA).
+    RatedCurrent { sum_irm: f64, ikss: f64 },
+
+    /// Motors fed through two-winding transformers may be neglected if the ratio of the sum of
+    /// their rated active power (MW) to the sum of the rated apparent power of the transformers
+    /// (MVA) is below the limit given by the short-circuit power at the feeder connection point.
+    Transformers {
+        sum_prm: f64,
+        sum_srt: f64,
+        limit: f64,
+    },
+
+    /// Neither criterion applies and the motors must be considered.
+    #[default]
+    NotApplicable,
+}
+
+impl MotorCriterion {
+    /// Returns true if the motor contribution may be neglected.
+    pub fn neglect(&self) -> bool {
+        match *self {
+            MotorCriterion::RatedCurrent { sum_irm, ikss } => sum_irm <= 0.01 * ikss,
+            MotorCriterion::Transformers {
+                sum_prm,
+                sum_srt,
+                limit,
+            } => sum_prm / sum_srt <= limit,
+            MotorCriterion::NotApplicable => false,
+        }
+    }
+}
+
+/// Upper limit of the ratio of motor power to transformer rating for motors fed through
+/// two-winding transformers to be neglected.
+///
+/// `sum_srt` is the rated apparent power of the transformers (MVA), `unq` the nominal system
+/// voltage (kV) and `ikss_q` the initial symmetrical short-circuit current (kA) without motors
+/// at the feeder connection point.
+pub(crate) fn transformer_limit(c: f64, sum_srt: f64, unq: f64, ikss_q: f64) -> f64 {
+    let sk = (c * 100.0 * sum_srt) / (SQRT_3 * unq * ikss_q);
+
+    0.8 / (sk - 0.3).abs() // (30)
+}
@@ -0,0 +1,40 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::traits::CurrentSource;
+
+/// Power station unit with full size converter (e.g. photovoltaic or battery storage),
+/// represented by a current source.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into, strip_option))]
+pub struct FullConverterUnit<N: Default> {
+    /// Connection point on the high-voltage side of the unit transformer.
+    pub node: N,
+
+    /// Source current of the unit in case of a three-phase short circuit, given by the
+    /// manufacturer (kA).
+    pub iskpf: f64,
+
+    /// Maximum steady-state short-circuit current (kA).
+    pub ikpfmax: f64,
+}
+
+impl<N: Clone + Default> FullConverterUnit<N> {
+    pub fn new() -> FullConverterUnitBuilder<N> {
+        FullConverterUnitBuilder::default()
+    }
+}
+
+impl<N: Default> CurrentSource<N> for FullConverterUnit<N> {
+    fn node(&self) -> &N {
+        &self.node
+    }
+
+    fn isk(&self) -> f64 {
+        self.iskpf
+    }
+
+    fn ikmax(&self) -> f64 {
+        self.ikpfmax
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::{format_err, Result};
+use derive_builder::Builder;
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+use crate::factors::mu;
+use crate::math::SQRT_3;
+use crate::{cmplx, traits::Sq};
+
+/// Reversible static converter-fed drive, treated as an asynchronous motor for the
+/// calculation of short-circuit currents.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into, strip_option))]
+pub struct ConverterDrive<N: Default> {
+    pub node: N,
+
+    /// Rated voltage of the converter transformer on the network side (kV). The rated voltage
+    /// of the converter is used if there is no transformer.
+    pub ur: f64,
+
+    /// Rated current of the converter transformer on the network side (kA). The rated current
+    /// of the converter is used if there is no transformer.
+    pub ir: f64,
+
+    /// Ratio of the locked-rotor current to the rated current.
+    #[builder(default = "3.0")]
+    pub ilr_ir: f64,
+
+    /// R/X ratio of the short-circuit impedance.
+    #[builder(default = "0.1")]
+    pub rx: f64,
+}
+
+impl<N: Clone + Default> ConverterDrive<N> {
+    pub fn new() -> ConverterDriveBuilder<N> {
+        ConverterDriveBuilder::default()
+    }
+
+    pub fn impedance(&self) -> Result<Complex64> {
+        let ur = self.ur * 1e3;
+        let ir = self.ir * 1e3;
+        if ir == 0.0 {
+            return Err(format_err!("rated current must be specified"));
+        }
+
+        let zm = (1.0 / self.ilr_ir) * (ur / (SQRT_3 * ir)); // (26)
+
+        let xm = zm / (1.0 + self.rx.sq()).sqrt(); // (27)
+        let rm = self.rx * xm;
+
+        Ok(cmplx!(rm, xm))
+    }
+
+    /// Symmetrical short-circuit breaking current (kA) of the drive for the given partial
+    /// initial short-circuit current (kA) and minimum time delay (s). The rated active power
+    /// per pair of poles is unknown and the factor q is taken as 1.
+    pub fn breaking_current(&self, ikss: f64, tmin: f64) -> f64 {
+        mu(ikss / self.ir, tmin) * ikss // (72)
+    }
+}
@@ -0,0 +1,168 @@
+use std::f64::consts::PI;
+
+use derive_builder::Builder;
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+use crate::cmplx;
+use crate::math::SQRT_3;
+use crate::tower::{earth_return_depth, MU_0};
+
+/// Metallic sheath of a cable, earthed at both ends.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into, strip_option))]
+pub struct CableSheath {
+    /// Resistance of the sheath of a single cable (Ohms/km).
+    pub r: f64,
+
+    /// Mean radius of the sheath (m).
+    pub radius: f64,
+
+    /// Distance between the axes of three single-core cables in trefoil (m). The sheaths of
+    /// the three cables are in parallel. Not used for three-core cables.
+    pub d: Option<f64>,
+
+    /// Earth resistivity (Ohm m).
+    #[builder(default = "100.0")]
+    pub rho: f64,
+}
+
+impl CableSheath {
+    pub fn new() -> CableSheathBuilder {
+        CableSheathBuilder::default()
+    }
+
+    /// Reduction factor of the sheath, the part of the zero-sequence current 3I0 of the cable
+    /// returning through earth: r = 1 - Z'LS / Z'S.
+    pub fn reduction_factor(&self, f: f64) -> Complex64 {
+        let omega = 2.0 * PI * f;
+        let delta = earth_return_depth(f, self.rho);
+
+        let (r, radius) = match self.d {
+            Some(d) => (self.r / 3.0, (self.radius * d * d).cbrt()),
+            None => (self.r, self.radius),
+        };
+
+        // Mutual impedance between the conductors and the sheath (Ohms/km).
+        let z_ls = cmplx!(
+            omega * MU_0 / 8.0 * 1e3,
+            omega * MU_0 / (2.0 * PI) * (delta / radius).ln() * 1e3
+        );
+        let z_s = cmplx!(r) + z_ls;
+
+        cmplx!(r) / z_s
+    }
+}
+
+/// Split of the current of a line-to-earth short circuit between the earth and the earth
+/// wires or cable sheaths of the line.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct EarthReturn {
+    /// Reduction factor.
+    pub r: Complex64,
+
+    /// Initial short-circuit current of the line-to-earth short circuit (kA).
+    pub ik1: f64,
+
+    /// Current returning through earth (kA).
+    pub ie: f64,
+
+    /// Current through the earth wires or cable sheaths (kA).
+    pub iq: f64,
+}
+
+impl EarthReturn {
+    /// Splits the initial short-circuit current `ik1` (kA) of a line-to-earth short circuit,
+    /// which equals 3I0, using the reduction factor `r`.
+    pub fn new(ik1: f64, r: Complex64) -> Self {
+        EarthReturn {
+            r,
+            ik1,
+            ie: r.norm() * ik1,
+            iq: (cmplx!(1) - r).norm() * ik1,
+        }
+    }
+}
+
+/// Double earth fault, two line-to-earth short circuits in different phases at different
+/// locations A and B of an isolated or resonant-earthed network.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DoubleEarthFault<N: Default> {
+    pub node_a: N,
+
+    pub node_b: N,
+
+    /// Initial short-circuit current of the double earth fault (kA).
+    pub ikee: f64,
+
+    /// Partial short-circuit currents of the overhead lines, the zero-sequence current 3I0
+    /// split between the earth and the earth wires.
+    pub lines: Vec<EarthReturn>,
+
+    /// Partial short-circuit currents of the cables, the zero-sequence current 3I0 split
+    /// between the earth and the sheath.
+    pub cables: Vec<EarthReturn>,
+}
+
+/// Arc suppression (Petersen) coil of a resonant-earthed network.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into))]
+pub struct CompensationCoil {
+    /// Detuning v = (IL - ICE) / ICE. Positive if the network is overcompensated.
+    pub v: f64,
+
+    /// Damping d = IRw / ICE, the ratio of the active residual current due to the losses of
+    /// the coil and the network to the capacitive earth fault current.
+    #[builder(default = "0.05")]
+    pub d: f64,
+}
+
+impl CompensationCoil {
+    pub fn new() -> CompensationCoilBuilder {
+        CompensationCoilBuilder::default()
+    }
+}
+
+/// Currents of a line-to-earth fault in an isolated or resonant-earthed network.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct CapacitiveEarthFault<N: Default> {
+    pub node: N,
+
+    /// Zero-sequence capacitance of the galvanically connected network (uF).
+    pub c0: f64,
+
+    /// Capacitive earth fault current (kA).
+    pub ice: f64,
+
+    /// Inductive current of the compensation coil (kA).
+    pub il: f64,
+
+    /// Residual current at the fault location (kA).
+    pub ires: f64,
+}
+
+impl<N: Default> CapacitiveEarthFault<N> {
+    /// Currents of an earth fault at `node` in a network with the zero-sequence capacitance
+    /// `c0` (uF) and the source voltage `c un` (kV) at frequency `f`, earthed through the
+    /// compensation coil, if given, or isolated otherwise.
+    pub fn new(node: N, c0: f64, c: f64, un: f64, f: f64, coil: Option<&CompensationCoil>) -> Self {
+        let omega = 2.0 * PI * f;
+        let ice = SQRT_3 * omega * c0 * c * un * 1e-6;
+
+        let (il, ires) = match coil {
+            Some(coil) => (
+                (1.0 + coil.v) * ice,
+                ice * (coil.d.powi(2) + coil.v.powi(2)).sqrt(),
+            ),
+            None => (0.0, ice),
+        };
+
+        CapacitiveEarthFault {
+            node,
+            c0,
+            ice,
+            il,
+            ires,
+        }
+    }
+}
@@ -6,18 +6,32 @@ use serde::{Deserialize, Serialize};
 pub struct Fault<N: Default> {
     pub node: N,
 
+    /// Initial symmetrical short-circuit current (kA).
+    pub ikss: f64,
+
     /// Peak short-circuit current (50Hz method) (kA).
     pub ip50: f64,
 
     /// Peak short-circuit current (20Hz method) (kA).
     pub ip20: f64,
 
-    /// Symmetrical short-circuit breaking current (r.m.s.)"]
+    /// Symmetrical short-circuit breaking current (kA).
     pub ib: f64,
 
-    /// Steady-state short-circuit current (r.m.s.)"]
+    /// Asymmetrical short-circuit breaking current (kA).
+    pub ib_asym: f64,
+
+    /// D.c. component of the short-circuit current at the minimum time delay (kA).
+    pub idc: f64,
+
+    /// R/X ratio of the short-circuit impedance for the d.c. component at the minimum time
+    /// delay (method C).
+    pub rx: f64,
+
+    /// Steady-state short-circuit current (kA).
     pub ik: f64,
 
-    /// Thermal equivalent short-circuit current (kA).
+    /// Thermal equivalent short-circuit current (kA), see
+    /// [`thermal_short_circuit`](crate::ACSystem::thermal_short_circuit).
     pub ith: f64,
 }
@@ -24,10 +24,15 @@ pub struct NetworkFeeder<N: Default> {
     pub rx: Option<f64>,
 
     /// Rated transformation ratio at which the on-load tap-changer is in the main position (>= 1).
+    /// Only used to refer the impedance by hand, [`ACSystem`](crate::ACSystem) refers each
+    /// element to the voltage level of its nodes.
     pub tr: Option<f64>,
 
+    /// Ratio of the zero-sequence resistance to the zero-sequence reactance.
     pub r0x: f64,
 
+    /// Ratio of the zero-sequence reactance to the positive-sequence reactance. The feeder
+    /// has no path for zero-sequence currents if zero.
     pub x0x: f64,
 }
 
@@ -36,17 +41,30 @@ impl<N: Clone + Default + Eq + core::hash::Hash> NetworkFeeder<N> {
         NetworkFeederBuilder::default()
     }
 
+    /// Short-circuit impedance (Ohms) referred to the low-voltage side of a transformer
+    /// with ratio `tr`, if given, or at the connection point otherwise.
     pub fn impedance(&self, ohl: bool, busbar_index: &BusbarIndex<N>) -> Result<Complex64> {
+        let z = self.unreferred_impedance(ohl, busbar_index)?;
+
+        Ok(match self.tr {
+            Some(tr) => z / tr.sq(),
+            None => z,
+        })
+    }
+
+    /// Short-circuit impedance (Ohms) at the voltage level of the connection point.
+    pub fn unreferred_impedance(
+        &self,
+        ohl: bool,
+        busbar_index: &BusbarIndex<N>,
+    ) -> Result<Complex64> {
         let busbar = busbar_index.busbar(&self.node).unwrap();
 
         let c = c_or_default(busbar);
         let un = busbar.un;
         let ikss = self.ikss;
 
-        let mut z = (c * un) / (SQRT_3 * ikss);
-        if let Some(tr) = self.tr {
-            z *= 1.0 / tr.sq();
-        }
+        let z = (c * un) / (SQRT_3 * ikss);
 
         let x = if let Some(rx) = self.rx {
             z / (1.0 + rx.sq()).sqrt()
@@ -67,4 +85,13 @@ impl<N: Clone + Default + Eq + core::hash::Hash> NetworkFeeder<N> {
 
         Ok(z)
     }
+
+    /// Zero-sequence short-circuit impedance (Ohms) at the voltage level of the connection
+    /// point.
+    pub fn zero_sequence_impedance(&self, busbar_index: &BusbarIndex<N>) -> Result<Complex64> {
+        let z = self.unreferred_impedance(false, busbar_index)?;
+        let x0 = self.x0x * z.im;
+
+        Ok(cmplx!(self.r0x * x0, x0))
+    }
 }
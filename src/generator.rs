@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::busbar::{c_or_default, voltage_correction_factor, BusbarIndex};
 use crate::cmplx;
+use crate::factors::{lambda_max, mu};
+use crate::math::SQRT_3;
 use crate::traits::Sq;
 
 /// A generator without a unit transformer.
@@ -31,6 +33,12 @@ pub struct SynchronousGenerator<N: Default> {
 
     /// Saturated synchronous reactance (p.u.).
     pub xdsat: f64,
+
+    /// Highest possible excitation voltage related to the excitation voltage at rated load and
+    /// power factor (p.u.).
+    #[builder(default = "1.3")]
+    pub ufmax: f64,
+
     /// Range of generator voltage regulation (%).
     #[builder(default = "0.0")]
     pub p: f64,
@@ -67,26 +75,69 @@ impl<N: Clone + Default + Eq + core::hash::Hash> SynchronousGenerator<N> {
         let pg = self.p / 100.0;
 
         if (un - ur_g).abs() > tol * 1e3 {
-            ur_g = ur_g * (1.0 + pg); // For three phase short-circuit currents.
+            ur_g *= 1.0 + pg; // For three phase short-circuit currents.
         }
         let zr_g = ur_g.sq() / sr_g;
         let xdpp = xdpp_pu * zr_g; // Ohms
 
-        let kg = (un / ur_g) * (c / (1.0 + xdpp_pu * phi.sin())); // Correction factor (18).
+        let kg = correction_factor(un, ur_g, c, xdpp_pu, phi.sin()); // Correction factor (18).
 
         let zg = cmplx!(rg, xdpp); // Subtransient impedance of the generator in the positive-sequence system.
 
         let mut zg_k = zg * cmplx!(kg); // Corrected subtransient impedance of the generator (17).
 
         if peak {
-            let rgf = match ur_g {
-                _ if ur_g > 1e3 && sr_g >= 100e6 => 0.05 * xdpp,
-                _ if ur_g > 1e3 && sr_g < 100e6 => 0.07 * xdpp,
-                _ => 0.15 * xdpp,
-            };
+            let rgf = fictitious_resistance(ur_g, sr_g, xdpp);
             zg_k = cmplx!(rgf, xdpp);
         }
 
         Ok(zg_k)
     }
+
+    /// Subtransient impedance of the generator without correction factor (Ohms).
+    pub fn uncorrected_impedance(&self) -> Complex64 {
+        let ur = self.ur * 1e3;
+        let sr = self.sr * 1e3;
+
+        cmplx!(self.r, self.xdpp * ur.sq() / sr)
+    }
+
+    /// Rated current of the generator (kA).
+    pub fn ir(&self) -> f64 {
+        self.sr / (SQRT_3 * self.ur) / 1e3
+    }
+
+    /// Symmetrical short-circuit breaking current (kA) of the generator for the given partial
+    /// initial short-circuit current (kA) and minimum time delay (s).
+    pub fn breaking_current(&self, ikss: f64, tmin: f64) -> f64 {
+        mu(ikss / self.ir(), tmin) * ikss // (71)
+    }
+
+    /// Maximum steady-state short-circuit current (kA) of the generator for the given partial
+    /// initial short-circuit current (kA).
+    pub fn steady_state_current(&self, ikss: f64) -> f64 {
+        let sin_phi = self.cos_phi.acos().sin();
+        let ir = self.ir();
+
+        lambda_max(ikss / ir, self.xdpp, self.xdsat, sin_phi, self.ufmax) * ir // (83)
+    }
+}
+
+/// Correction factor for the impedance of a synchronous machine connected directly to the
+/// network (18). `un` and `ur` are the nominal system and rated machine voltages, `c` the
+/// voltage factor, `xdpp` the relative subtransient reactance and `sin_phi` the sine of the
+/// rated power factor angle.
+pub(crate) fn correction_factor(un: f64, ur: f64, c: f64, xdpp: f64, sin_phi: f64) -> f64 {
+    (un / ur) * (c / (1.0 + xdpp * sin_phi))
+}
+
+/// Fictitious resistance (Ohms) of a synchronous machine used for the calculation of the peak
+/// short-circuit current, given the rated voltage (V), rated apparent power (VA) and
+/// subtransient reactance (Ohms).
+pub(crate) fn fictitious_resistance(ur: f64, sr: f64, xdpp: f64) -> f64 {
+    match ur {
+        _ if ur > 1e3 && sr >= 100e6 => 0.05 * xdpp,
+        _ if ur > 1e3 && sr < 100e6 => 0.07 * xdpp,
+        _ => 0.15 * xdpp,
+    }
 }
@@ -1,20 +1,39 @@
 //! Short-circuit currents in three-phase a.c. systems.
 
+// Element constructors return their builders.
+#![allow(clippy::new_ret_no_self)]
+
 mod ac_system;
 mod busbar;
 mod math;
 mod traits;
 
 mod cable;
+mod capacitor;
+mod contribution;
+mod converter;
+mod drive;
+mod earth;
+mod factors;
 mod fault;
 mod feeder;
 mod generator;
 mod line;
+mod load;
 mod motor;
+mod motor_group;
+mod per_unit;
+mod prefault;
+mod rating;
 mod reactor;
 mod station;
+mod synchronous_motor;
+mod tap;
+mod tower;
 mod transformer;
 mod transformer3;
+mod waveform;
+mod wind;
 
 pub mod part4;
 
@@ -24,30 +43,75 @@ mod tests;
 pub use ac_system::ACSystem;
 pub use busbar::{Busbar, BusbarIndex};
 
-pub use cable::Cable;
+pub use cable::{Cable, CableThermalCheck, ConductorMaterial, Insulation};
+pub use capacitor::SeriesCapacitor;
+pub use contribution::{MotorContribution, MotorCriterion};
+pub use converter::FullConverterUnit;
+pub use drive::ConverterDrive;
+pub use earth::{
+    CableSheath, CapacitiveEarthFault, CompensationCoil, DoubleEarthFault, EarthReturn,
+};
 pub use fault::Fault;
 pub use feeder::NetworkFeeder;
 pub use generator::SynchronousGenerator;
-pub use line::OverheadLine;
+pub use line::{LineCoupling, OverheadLine};
+pub use load::Load;
 pub use motor::AsynchronousMotor;
+pub use motor_group::LowVoltageMotorGroup;
+pub use per_unit::PerUnit;
+pub use prefault::{NodeVoltage, OperatingPoint, PowerInjection, SuperpositionFault};
+pub use rating::{Branch, Duty, EquipmentRating, RatingEvaluation};
 pub use reactor::Reactor;
 pub use station::PowerStationUnit;
-pub use transformer::NetworkTransformer;
+pub use synchronous_motor::SynchronousMotor;
+pub use tap::{TapChanger, TapSelection};
+pub use tower::{Conductor, TowerGeometry};
+pub use transformer::{NetworkTransformer, ZeroSequenceConnection};
 pub use transformer3::{ThreeWindingTransformer, TransformerSide, TransformerSides};
+pub use waveform::Waveform;
+pub use wind::{DoublyFedWindUnit, FullConverterWindUnit};
 
 pub mod builder {
     pub use crate::ac_system::{ACSystemBuilder, ACSystemBuilderError};
     pub use crate::busbar::{BusbarBuilder, BusbarBuilderError};
 
     pub use crate::cable::{CableBuilder, CableBuilderError};
+    pub use crate::capacitor::{SeriesCapacitorBuilder, SeriesCapacitorBuilderError};
+    pub use crate::converter::{FullConverterUnitBuilder, FullConverterUnitBuilderError};
+    pub use crate::drive::{ConverterDriveBuilder, ConverterDriveBuilderError};
+    pub use crate::earth::{
+        CableSheathBuilder, CableSheathBuilderError, CompensationCoilBuilder,
+        CompensationCoilBuilderError,
+    };
     pub use crate::fault::{FaultBuilder, FaultBuilderError};
     pub use crate::feeder::{NetworkFeederBuilder, NetworkFeederBuilderError};
     pub use crate::generator::{SynchronousGeneratorBuilder, SynchronousGeneratorBuilderError};
-    pub use crate::line::{OverheadLineBuilder, OverheadLineBuilderError};
+    pub use crate::line::{
+        LineCouplingBuilder, LineCouplingBuilderError, OverheadLineBuilder,
+        OverheadLineBuilderError,
+    };
+    pub use crate::load::{LoadBuilder, LoadBuilderError};
     pub use crate::motor::{AsynchronousMotorBuilder, AsynchronousMotorBuilderError};
+    pub use crate::motor_group::{LowVoltageMotorGroupBuilder, LowVoltageMotorGroupBuilderError};
+    pub use crate::per_unit::{PerUnitBuilder, PerUnitBuilderError};
+    pub use crate::prefault::{
+        NodeVoltageBuilder, NodeVoltageBuilderError, OperatingPointBuilder,
+        OperatingPointBuilderError, PowerInjectionBuilder, PowerInjectionBuilderError,
+    };
+    pub use crate::rating::{EquipmentRatingBuilder, EquipmentRatingBuilderError};
     pub use crate::reactor::{ReactorBuilder, ReactorBuilderError};
+    pub use crate::station::{PowerStationUnitBuilder, PowerStationUnitBuilderError};
+    pub use crate::synchronous_motor::{SynchronousMotorBuilder, SynchronousMotorBuilderError};
+    pub use crate::tap::{TapChangerBuilder, TapChangerBuilderError};
+    pub use crate::tower::{
+        ConductorBuilder, ConductorBuilderError, TowerGeometryBuilder, TowerGeometryBuilderError,
+    };
     pub use crate::transformer::{NetworkTransformerBuilder, NetworkTransformerBuilderError};
     pub use crate::transformer3::{
         ThreeWindingTransformerBuilder, ThreeWindingTransformerBuilderError,
     };
+    pub use crate::wind::{
+        DoublyFedWindUnitBuilder, DoublyFedWindUnitBuilderError, FullConverterWindUnitBuilder,
+        FullConverterWindUnitBuilderError,
+    };
 }
@@ -6,6 +6,7 @@ use num_complex::Complex64;
 use serde::{Deserialize, Serialize};
 
 use crate::cmplx;
+use crate::tower::TowerGeometry;
 
 #[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
 #[builder(default, setter(into, strip_option))]
@@ -30,9 +31,21 @@ pub struct OverheadLine<N: Default> {
     /// Zero-sequence short-circuit reactance (Ohms/km).
     pub x0: f64,
 
+    /// Positive-sequence capacitance (uF/km).
+    pub c1: f64,
+
+    /// Zero-sequence capacitance, the capacitance of each conductor to earth (uF/km).
+    pub c0: f64,
+
     /// Number of parallel lines.
     pub parallel: Option<i64>,
 
+    /// Mutual zero-sequence resistance between the parallel lines (Ohms/km).
+    pub r0m: f64,
+
+    /// Mutual zero-sequence reactance between the parallel lines (Ohms/km).
+    pub x0m: f64,
+
     /// Cross-section (mm^2).
     pub qn: f64,
 
@@ -44,6 +57,10 @@ pub struct OverheadLine<N: Default> {
 
     /// Number of bundled conductors.
     pub n: Option<i64>,
+
+    /// Arrangement of the conductors on the tower. If given, the positive and zero-sequence
+    /// impedances are calculated from the geometry with Carson's equations.
+    pub geometry: Option<TowerGeometry>,
 }
 
 impl<N: Clone + Default> OverheadLine<N> {
@@ -52,7 +69,10 @@ impl<N: Clone + Default> OverheadLine<N> {
     }
 
     pub fn impedance(&self, f: f64) -> Result<Complex64> {
-        let (rl, xl) = if let Some(d) = self.d {
+        let (rl, xl) = if let Some(geometry) = &self.geometry {
+            let (z1, _) = geometry.sequence_impedances(f)?;
+            (z1.re, z1.im)
+        } else if let Some(d) = self.d {
             let rho = self.rho;
             let qn = self.qn;
             let n = self.n.unwrap_or(1) as f64;
@@ -77,4 +97,69 @@ impl<N: Clone + Default> OverheadLine<N> {
 
         Ok(zl)
     }
+
+    /// Zero-sequence short-circuit impedance (Ohms). The parallel lines are coupled by the
+    /// mutual zero-sequence impedance: Z(0) = (Z(0)L + (n - 1) Z(0)M) / n.
+    pub fn zero_sequence_impedance(&self, f: f64) -> Result<Complex64> {
+        let (r0, x0) = if let Some(geometry) = &self.geometry {
+            let (_, z0) = geometry.sequence_impedances(f)?;
+            (z0.re, z0.im)
+        } else {
+            (self.r0, self.x0)
+        };
+
+        let mut z0 = cmplx!(r0, x0) * cmplx!(self.l);
+        if let Some(p) = self.parallel {
+            let z0m = cmplx!(self.r0m, self.x0m) * cmplx!(self.l);
+            z0 = (z0 + z0m * cmplx!(p - 1)) / cmplx!(p);
+        }
+
+        Ok(z0)
+    }
+
+    /// Reduction factor of the earth wires, the part of the zero-sequence current returning
+    /// through earth. Without tower geometry the line has no earth wires and the factor is 1.
+    pub fn reduction_factor(&self, f: f64) -> Result<Complex64> {
+        match &self.geometry {
+            Some(geometry) => geometry.reduction_factor(f),
+            None => Ok(cmplx!(1)),
+        }
+    }
+
+    /// Positive and zero-sequence capacitances (uF) of the parallel lines.
+    pub fn capacitances(&self) -> (f64, f64) {
+        let n = self.parallel.unwrap_or(1) as f64;
+
+        (self.c1 * self.l * n, self.c0 * self.l * n)
+    }
+}
+
+/// Mutual zero-sequence coupling between two overhead lines, such as two circuits on the
+/// same tower. The lines are assumed to run in parallel over the length of the shorter line.
+/// Lines with more than two circuits on a tower are described by a coupling for each pair.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into))]
+pub struct LineCoupling {
+    /// Index of the first line.
+    pub line_i: usize,
+
+    /// Index of the second line.
+    pub line_j: usize,
+
+    /// Mutual zero-sequence resistance (Ohms/km).
+    pub r0m: f64,
+
+    /// Mutual zero-sequence reactance (Ohms/km).
+    pub x0m: f64,
+}
+
+impl LineCoupling {
+    pub fn new() -> LineCouplingBuilder {
+        LineCouplingBuilder::default()
+    }
+
+    /// Mutual zero-sequence impedance (Ohms) over the length `l` (km).
+    pub fn impedance(&self, l: f64) -> Complex64 {
+        cmplx!(self.r0m, self.x0m) * cmplx!(l)
+    }
 }
@@ -0,0 +1,40 @@
+use derive_builder::Builder;
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+use crate::cmplx;
+
+/// Static load with exponential voltage dependence. Loads are neglected by the equivalent
+/// voltage source method and only define the operating conditions before the short circuit.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into, strip_option))]
+pub struct Load<N: Default> {
+    pub node: N,
+
+    /// Active power at the nominal system voltage (MW).
+    pub p: f64,
+
+    /// Reactive power at the nominal system voltage (Mvar).
+    pub q: f64,
+
+    /// Voltage exponent of the active power: 0 for constant power, 1 for constant current
+    /// and 2 for constant impedance.
+    pub np: f64,
+
+    /// Voltage exponent of the reactive power.
+    pub nq: f64,
+}
+
+impl<N: Clone + Default> Load<N> {
+    pub fn new() -> LoadBuilder<N> {
+        LoadBuilder::default()
+    }
+
+    /// Three-phase power (MVA) consumed by the load at the voltage `u` for the nominal
+    /// system voltage `un` (kV).
+    pub fn power(&self, u: f64, un: f64) -> Complex64 {
+        let u = u / un;
+
+        cmplx!(self.p * u.powf(self.np), self.q * u.powf(self.nq))
+    }
+}
@@ -1,5 +1,10 @@
+use anyhow::{format_err, Result};
+use num_complex::Complex64;
+
 pub const SQRT_3: f64 = 1.732050807568877293527446341505872366942805253810380628055;
 
+pub(crate) const ONE: Complex64 = Complex64 { re: 1.0, im: 0.0 };
+
 #[macro_export]
 macro_rules! cmplx {
     () => {
@@ -12,3 +17,45 @@ macro_rules! cmplx {
         num_complex::Complex64::new($arg1 as f64, $arg2 as f64)
     };
 }
+
+/// Inverse of the square matrix `a` by Gauss-Jordan elimination with partial pivoting.
+pub(crate) fn invert(a: &[Vec<Complex64>]) -> Result<Vec<Vec<Complex64>>> {
+    let n = a.len();
+    let mut a = a.to_vec();
+    let mut inv: Vec<Vec<Complex64>> = (0..n)
+        .map(|i| {
+            let mut row = vec![Complex64::default(); n];
+            row[i] = ONE;
+            row
+        })
+        .collect();
+
+    for k in 0..n {
+        let p = (k..n)
+            .max_by(|&i, &j| a[i][k].norm().total_cmp(&a[j][k].norm()))
+            .unwrap();
+        if a[p][k].norm() == 0.0 {
+            return Err(format_err!("singular matrix"));
+        }
+        a.swap(k, p);
+        inv.swap(k, p);
+
+        let d = a[k][k];
+        for j in 0..n {
+            a[k][j] /= d;
+            inv[k][j] /= d;
+        }
+        for i in 0..n {
+            if i == k {
+                continue;
+            }
+            let f = a[i][k];
+            for j in 0..n {
+                let (akj, invkj) = (a[k][j], inv[k][j]);
+                a[i][j] -= f * akj;
+                inv[i][j] -= f * invkj;
+            }
+        }
+    }
+    Ok(inv)
+}
@@ -3,6 +3,8 @@ use derive_builder::Builder;
 use num_complex::Complex64;
 use serde::{Deserialize, Serialize};
 
+use crate::factors::{mu, q};
+use crate::math::SQRT_3;
 use crate::{cmplx, traits::Sq};
 
 #[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
@@ -44,6 +46,14 @@ impl<N: Clone + Default> AsynchronousMotor<N> {
         AsynchronousMotorBuilder::default()
     }
 
+    /// Sum of the rated currents of the motors in the group (kA).
+    pub fn ir(&self) -> f64 {
+        let eta = self.eta / 100.0;
+        let n = self.n as f64;
+
+        n * self.pr / (SQRT_3 * self.ur * eta * self.cos_phi) / 1e3
+    }
+
     pub fn impedance(&self) -> Result<Complex64> {
         let ur = self.ur * 1e3;
         let pr = self.pr * 1e3;
@@ -80,4 +90,12 @@ impl<N: Clone + Default> AsynchronousMotor<N> {
 
         Ok(cmplx!(rm, xm))
     }
+
+    /// Symmetrical short-circuit breaking current (kA) of the motor group for the given partial
+    /// initial short-circuit current (kA) and minimum time delay (s).
+    pub fn breaking_current(&self, ikss: f64, tmin: f64) -> f64 {
+        let m = (self.pr / 1e3) / self.p as f64; // Rated active power per pair of poles (MW).
+
+        mu(ikss / self.ir(), tmin) * q(m, tmin) * ikss // (72)
+    }
 }
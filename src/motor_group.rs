@@ -0,0 +1,71 @@
+use anyhow::{format_err, Result};
+use derive_builder::Builder;
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+use crate::factors::{mu, q};
+use crate::math::SQRT_3;
+use crate::{cmplx, traits::Sq};
+
+/// Group of low-voltage asynchronous motors, including their connecting cables, combined
+/// into an equivalent motor.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into, strip_option))]
+pub struct LowVoltageMotorGroup<N: Default> {
+    pub node: N,
+
+    /// Rated voltage of the motors (kV).
+    pub ur: f64,
+
+    /// Rated currents of the motors in the group (kA).
+    #[builder(setter(each(name = "motor")))]
+    pub irm: Vec<f64>,
+
+    /// Rated active powers of the motors in the group (kW).
+    #[builder(setter(each(name = "motor_power")))]
+    pub prm: Vec<f64>,
+
+    /// Rated active power per pair of poles (MW).
+    #[builder(default = "0.05")]
+    pub m: f64,
+}
+
+impl<N: Clone + Default> LowVoltageMotorGroup<N> {
+    pub fn new() -> LowVoltageMotorGroupBuilder<N> {
+        LowVoltageMotorGroupBuilder::default()
+    }
+
+    /// Rated current of the equivalent motor (kA).
+    pub fn ir(&self) -> f64 {
+        self.irm.iter().sum()
+    }
+
+    /// Rated active power of the equivalent motor (kW).
+    pub fn pr(&self) -> f64 {
+        self.prm.iter().sum()
+    }
+
+    pub fn impedance(&self) -> Result<Complex64> {
+        let ur = self.ur * 1e3;
+        let ir = self.ir() * 1e3;
+        if ir == 0.0 {
+            return Err(format_err!("rated current must be specified"));
+        }
+
+        let ilr_ir = 5.0;
+        let rx = 0.42;
+
+        let zm = (1.0 / ilr_ir) * (ur / (SQRT_3 * ir)); // (26)
+
+        let xm = zm / (1.0 + rx.sq()).sqrt(); // (27)
+        let rm = xm * rx;
+
+        Ok(cmplx!(rm, xm))
+    }
+
+    /// Symmetrical short-circuit breaking current (kA) of the equivalent motor for the given
+    /// partial initial short-circuit current (kA) and minimum time delay (s).
+    pub fn breaking_current(&self, ikss: f64, tmin: f64) -> f64 {
+        mu(ikss / self.ir(), tmin) * q(self.m, tmin) * ikss // (72)
+    }
+}
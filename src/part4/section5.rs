@@ -36,7 +36,10 @@ pub fn iec60909_4_5() -> Result<ACSystem<&'static str>> {
         .sr(250.0 * 1e3) // 250MVA
         .ur_hv(240) //250,
         .ur_lv(21)
-        //.p(12) // %
+        .tap(
+            // On-load tap-changer, pT = ±12%
+            TapChanger::new().step(1.0).min(-12).max(12).build()?,
+        )
         .ukr(15) // %
         .pkr(520) // 520kW
         .build()?;
@@ -266,10 +269,12 @@ pub fn iec60909_4_5() -> Result<ACSystem<&'static str>> {
         .frequency(50)
         .busbars([q, b, c])
         .feeders([network])
-        .power_station(PowerStationUnit {
-            generator: g,
-            transformer: t,
-        })
+        .power_station(
+            PowerStationUnit::new()
+                .generator(g)
+                .transformer(t)
+                .build()?,
+        )
         .transformers([
             t15_19("T15", "M15"),
             t15_19("T16", "M16"),
@@ -35,7 +35,10 @@ pub fn iec60909_4_6() -> Result<ACSystem<&'static str>> {
         .sr(150_000)
         .ukr(16)
         .urr(0.5)
-        .p(12) // YNd5 with on-load tap-changer
+        .tap(
+            // YNd5 with on-load tap-changer, pT = ±12%
+            TapChanger::new().step(1.0).min(-12).max(12).build()?,
+        )
         .x0x(0.95)
         .r0r(1.0)
         .build()?;
@@ -249,14 +252,18 @@ pub fn iec60909_4_6() -> Result<ACSystem<&'static str>> {
         .frequency(50)
         .busbars([b1, b2, b8, b5, b6, b7, b3, b4])
         .feeders([q1, q2])
-        .power_station(PowerStationUnit {
-            generator: g1,
-            transformer: t1,
-        })
-        .power_station(PowerStationUnit {
-            generator: g2,
-            transformer: t2,
-        })
+        .power_station(
+            PowerStationUnit::new()
+                .generator(g1)
+                .transformer(t1)
+                .build()?,
+        )
+        .power_station(
+            PowerStationUnit::new()
+                .generator(g2)
+                .transformer(t2)
+                .build()?,
+        )
         .generator(g3)
         .three_winding_transformers([t3, t4])
         .transformers([t5, t6])
@@ -0,0 +1,58 @@
+use derive_builder::Builder;
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+use crate::fault::Fault;
+use crate::math::SQRT_3;
+
+/// Per-unit system on a common apparent power base. The base voltage of each node is the
+/// nominal system voltage of its busbar.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into))]
+pub struct PerUnit {
+    /// Base apparent power (MVA).
+    #[builder(default = "100.0")]
+    pub sbase: f64,
+}
+
+impl PerUnit {
+    pub fn new() -> PerUnitBuilder {
+        PerUnitBuilder::default()
+    }
+
+    /// Base impedance (Ohms) at the nominal system voltage `un` (kV).
+    pub fn zbase(&self, un: f64) -> f64 {
+        un * un / self.sbase
+    }
+
+    /// Base current (kA) at the nominal system voltage `un` (kV).
+    pub fn ibase(&self, un: f64) -> f64 {
+        self.sbase / (SQRT_3 * un)
+    }
+
+    /// Impedance `z` (Ohms) at the nominal system voltage `un` (kV) in p.u.
+    pub fn impedance(&self, z: Complex64, un: f64) -> Complex64 {
+        z / self.zbase(un)
+    }
+
+    /// Current `i` (kA) at the nominal system voltage `un` (kV) in p.u.
+    pub fn current(&self, i: f64, un: f64) -> f64 {
+        i / self.ibase(un)
+    }
+
+    /// Short-circuit currents of `fault` at the nominal system voltage `un` (kV) in p.u.
+    pub fn fault<N: Clone + Default>(&self, fault: &Fault<N>, un: f64) -> Fault<N> {
+        Fault {
+            node: fault.node.clone(),
+            ikss: self.current(fault.ikss, un),
+            ip50: self.current(fault.ip50, un),
+            ip20: self.current(fault.ip20, un),
+            ib: self.current(fault.ib, un),
+            ib_asym: self.current(fault.ib_asym, un),
+            idc: self.current(fault.idc, un),
+            rx: fault.rx,
+            ik: self.current(fault.ik, un),
+            ith: self.current(fault.ith, un),
+        }
+    }
+}
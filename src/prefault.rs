@@ -0,0 +1,92 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+/// Operating conditions before the short circuit for the superposition method.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into))]
+pub struct OperatingPoint<N: Clone + Default> {
+    /// Active and reactive power set-points of the loads and generation.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "injection")))]
+    pub injections: Vec<PowerInjection<N>>,
+
+    /// Known pre-fault voltages. If any are given the load flow is not solved and busbars
+    /// without a voltage are assumed to operate at the nominal system voltage.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "voltage")))]
+    pub voltages: Vec<NodeVoltage<N>>,
+
+    /// Source voltage of the network feeders related to the nominal system voltage (p.u.).
+    #[builder(default = "1.0")]
+    pub uq: f64,
+
+    /// Convergence tolerance of the load flow (kV).
+    #[builder(default = "1e-9")]
+    pub tolerance: f64,
+
+    /// Maximum number of load flow iterations.
+    #[builder(default = "100")]
+    pub max_iter: usize,
+}
+
+impl<N: Clone + Default> OperatingPoint<N> {
+    pub fn new() -> OperatingPointBuilder<N> {
+        OperatingPointBuilder::default()
+    }
+}
+
+/// Three-phase power injected at a node. Generation is positive and load is negative.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into))]
+pub struct PowerInjection<N: Default> {
+    pub node: N,
+
+    /// Active power (MW).
+    pub p: f64,
+
+    /// Reactive power (Mvar).
+    pub q: f64,
+}
+
+impl<N: Clone + Default> PowerInjection<N> {
+    pub fn new() -> PowerInjectionBuilder<N> {
+        PowerInjectionBuilder::default()
+    }
+}
+
+/// Line-to-line voltage at a node before the short circuit.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into))]
+pub struct NodeVoltage<N: Default> {
+    pub node: N,
+
+    /// Voltage magnitude (kV).
+    pub u: f64,
+
+    /// Voltage angle (degrees).
+    pub angle: f64,
+}
+
+impl<N: Clone + Default> NodeVoltage<N> {
+    pub fn new() -> NodeVoltageBuilder<N> {
+        NodeVoltageBuilder::default()
+    }
+}
+
+/// Short-circuit currents of a balanced three-phase short circuit calculated with the
+/// superposition method. Only the initial symmetrical and peak short-circuit currents are
+/// supported; the breaking and steady-state currents are calculated with the equivalent
+/// voltage source by `ACSystem::short_circuit`.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct SuperpositionFault<N: Default> {
+    pub node: N,
+
+    /// Pre-fault line-to-line voltage at the short-circuit location (kV).
+    pub ub: f64,
+
+    /// Initial symmetrical short-circuit current (kA).
+    pub ikss: f64,
+
+    /// Peak short-circuit current (kA), from the R/X ratio at the short-circuit location.
+    pub ip: f64,
+}
@@ -0,0 +1,143 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+/// Short-circuit ratings of switchgear or other equipment at a busbar or at the end of a
+/// branch. Equipment at a busbar is evaluated against the short-circuit currents at the
+/// busbar. Equipment in a branch is evaluated against the partial short-circuit currents
+/// through the branch, for a short circuit on either side of the equipment.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into, strip_option))]
+pub struct EquipmentRating<N: Default> {
+    /// Node of the busbar the equipment is connected to.
+    pub node: N,
+
+    /// Branch the equipment is installed in, at its end on the busbar.
+    pub branch: Option<Branch>,
+
+    /// Rated short-circuit breaking current (kA).
+    pub isc: Option<f64>,
+
+    /// Rated short-circuit making current (kA, peak).
+    pub ima: Option<f64>,
+
+    /// Rated short-time withstand current (kA).
+    pub ithr: Option<f64>,
+
+    /// Rated duration of short circuit (s).
+    #[builder(setter(strip_option = false), default = "1.0")]
+    pub tkr: f64,
+}
+
+impl<N: Clone + Default> EquipmentRating<N> {
+    pub fn new() -> EquipmentRatingBuilder<N> {
+        EquipmentRatingBuilder::default()
+    }
+}
+
+/// Branch of an [`ACSystem`](crate::ACSystem), by the index of the element.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub enum Branch {
+    Line(usize),
+
+    Cable(usize),
+
+    Transformer(usize),
+}
+
+/// Short-circuit duty of equipment.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub enum Duty {
+    /// Asymmetrical short-circuit breaking current Ib,asym against the rated breaking current.
+    Breaking,
+
+    /// Peak short-circuit current ip against the rated making current.
+    Making,
+
+    /// Thermal equivalent short-circuit current Ith against the rated short-time withstand
+    /// current.
+    ShortTime,
+}
+
+/// Evaluation of the short-circuit currents against the ratings of equipment.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct RatingEvaluation<N: Default> {
+    pub node: N,
+
+    pub branch: Option<Branch>,
+
+    /// Symmetrical short-circuit breaking current (kA).
+    pub ib: f64,
+
+    /// Asymmetrical short-circuit breaking current, including the d.c. component at the
+    /// minimum time delay (kA).
+    pub ib_asym: f64,
+
+    /// Peak short-circuit current (kA).
+    pub ip: f64,
+
+    /// Thermal equivalent short-circuit current for the duration of the short circuit (kA).
+    pub ith: f64,
+
+    /// Utilisation of the rated short-circuit breaking current by the asymmetrical breaking
+    /// current (%).
+    pub breaking: Option<f64>,
+
+    /// Utilisation of the rated short-circuit making current (%).
+    pub making: Option<f64>,
+
+    /// Utilisation of the rated short-time withstand current (%).
+    pub short_time: Option<f64>,
+
+    /// Duties exceeding the ratings.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<Duty>,
+}
+
+impl<N: Clone + Default> RatingEvaluation<N> {
+    /// Compares the asymmetrical breaking current `ib_asym` (kA), the peak current `ip` (kA)
+    /// and the thermal equivalent current `ith` (kA) for the duration of the short circuit
+    /// `tk` (s) with the ratings. The symmetrical breaking current `ib` (kA) is reported
+    /// only. For durations longer than the rated duration, Ith sqrt(Tk) is compared with
+    /// Ithr sqrt(Tkr).
+    pub fn new(
+        rating: &EquipmentRating<N>,
+        ib: f64,
+        ib_asym: f64,
+        ip: f64,
+        ith: f64,
+        tk: f64,
+    ) -> Self {
+        let utilisation = |i: f64, ir: Option<f64>| ir.map(|ir| 100.0 * i / ir);
+
+        let breaking = utilisation(ib_asym, rating.isc);
+        let making = utilisation(ip, rating.ima);
+        let short_time = if tk <= rating.tkr {
+            utilisation(ith, rating.ithr)
+        } else {
+            utilisation(ith * (tk / rating.tkr).sqrt(), rating.ithr)
+        };
+
+        let violations = [
+            (Duty::Breaking, breaking),
+            (Duty::Making, making),
+            (Duty::ShortTime, short_time),
+        ]
+        .into_iter()
+        .filter(|(_, u)| u.is_some_and(|u| u > 100.0))
+        .map(|(duty, _)| duty)
+        .collect();
+
+        RatingEvaluation {
+            node: rating.node.clone(),
+            branch: rating.branch,
+            ib,
+            ib_asym,
+            ip,
+            ith,
+            breaking,
+            making,
+            short_time,
+            violations,
+        }
+    }
+}
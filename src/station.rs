@@ -1,34 +1,99 @@
 use anyhow::{format_err, Result};
+use derive_builder::Builder;
 use num_complex::Complex64;
 use serde::{Deserialize, Serialize};
 
 use crate::busbar::{c_or_default, voltage_correction_factor, BusbarIndex};
-use crate::generator::SynchronousGenerator;
+use crate::generator::{fictitious_resistance, SynchronousGenerator};
+use crate::motor::AsynchronousMotor;
+use crate::motor_group::LowVoltageMotorGroup;
 use crate::traits::Sq;
 use crate::transformer::NetworkTransformer;
+use crate::transformer3::ThreeWindingTransformer;
 
 use crate::cmplx;
 
-/// Generator and unit transformer with on-load tap-changer.
-#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+/// Generator and unit transformer.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into))]
 pub struct PowerStationUnit<N: Default> {
     pub generator: SynchronousGenerator<N>,
 
     pub transformer: NetworkTransformer<N>,
+
+    /// Auxiliary transformers fed from the generator terminals or the auxiliary busbars.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "auxiliary_transformer")))]
+    pub auxiliary_transformers: Vec<NetworkTransformer<N>>,
+
+    /// Three-winding auxiliary transformers fed from the generator terminals.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "auxiliary_three_winding_transformer")))]
+    pub auxiliary_three_winding_transformers: Vec<ThreeWindingTransformer<N>>,
+
+    /// Medium and high-voltage motors connected to the auxiliary busbars.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "auxiliary_motor")))]
+    pub auxiliary_motors: Vec<AsynchronousMotor<N>>,
+
+    /// Low-voltage motor groups connected to the auxiliary busbars.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "auxiliary_motor_group")))]
+    pub auxiliary_motor_groups: Vec<LowVoltageMotorGroup<N>>,
 }
 
 impl<N: Clone + Default + Eq + core::hash::Hash> PowerStationUnit<N> {
+    pub fn new() -> PowerStationUnitBuilder<N> {
+        PowerStationUnitBuilder::default()
+    }
+
+    /// Does the unit transformer have an on-load tap-changer? The correction factor KS (22)
+    /// is used if true and KSO (24) otherwise.
+    pub fn oltc(&self) -> bool {
+        self.transformer.tap.as_ref().is_some_and(|tap| tap.oltc)
+    }
+
+    /// Nodes of the auxiliary network of the unit, excluding the generator terminals.
+    pub fn auxiliary_nodes(&self) -> Vec<&N> {
+        let mut nodes = vec![];
+        for t in &self.auxiliary_transformers {
+            nodes.extend([&t.node_hv, &t.node_lv]);
+        }
+        for t in &self.auxiliary_three_winding_transformers {
+            nodes.extend([&t.node_hv, &t.node_mv, &t.node_lv]);
+        }
+        nodes.extend(self.auxiliary_motors.iter().map(|m| &m.node));
+        nodes.extend(self.auxiliary_motor_groups.iter().map(|m| &m.node));
+
+        nodes.retain(|&n| *n != self.generator.node);
+        nodes
+    }
+
     pub fn impedance(
         &self,
         hv: bool,
-        oltc: bool,
         peak: bool,
         busbar_index: &BusbarIndex<N>,
     ) -> Result<Complex64> {
+        let (zg, zt) = self.impedances(hv, peak, busbar_index)?;
+
+        Ok(zg + zt)
+    }
+
+    /// Corrected impedances of the generator and the unit transformer, on the high-voltage
+    /// side if `hv` is true or the low-voltage side otherwise.
+    pub(crate) fn impedances(
+        &self,
+        hv: bool,
+        peak: bool,
+        busbar_index: &BusbarIndex<N>,
+    ) -> Result<(Complex64, Complex64)> {
         let t = &self.transformer;
         let g = &self.generator;
 
-        let busbar = busbar_index.busbar(&t.node_hv).unwrap();
+        let busbar = busbar_index
+            .busbar(&t.node_hv)
+            .ok_or_else(|| format_err!("high-voltage node not on a busbar"))?;
 
         let mut un = busbar.un * 1e3;
         let mut c = c_or_default(busbar);
@@ -48,7 +113,7 @@ impl<N: Clone + Default + Eq + core::hash::Hash> PowerStationUnit<N> {
 
         if t.ur_lv > ur_g {
             // step-up
-            ur_g = ur_g * (1.0 + pg); // For three phase short-circuit currents.
+            ur_g *= 1.0 + pg; // For three phase short-circuit currents.
         }
         let zr_g = ur_g.sq() / sr_g;
         let xdpp = xdpp_pu * zr_g; // Ohm
@@ -57,11 +122,7 @@ impl<N: Clone + Default + Eq + core::hash::Hash> PowerStationUnit<N> {
         let mut zg = cmplx!(rg, xdpp);
 
         if peak {
-            let rgf: f64 = match ur_g {
-                _ if ur_g > 1e3 && sr_g >= 100e6 => 0.05 * xdpp,
-                _ if ur_g > 1e3 && sr_g < 100e6 => 0.07 * xdpp,
-                _ => 0.15 * xdpp,
-            };
+            let rgf = fictitious_resistance(ur_g, sr_g, xdpp);
             zg = cmplx!(rgf, xdpp);
         }
 
@@ -73,11 +134,10 @@ impl<N: Clone + Default + Eq + core::hash::Hash> PowerStationUnit<N> {
         let sr_t = t.sr * 1e3;
         let pkr = t.pkr * 1e3;
 
-        let tr2 = cmplx!((t.ur_hv / t.ur_lv).sq());
-        let ur_t = if hv {
-            t.ur_hv * 1000.0
+        let (tr2, ur_t) = if hv {
+            (cmplx!((t.ur_hv / t.ur_lv).sq()), t.ur_hv * 1000.0)
         } else {
-            t.ur_lv * 1000.0
+            (cmplx!(1), t.ur_lv * 1000.0)
         };
 
         let z = (ukr / 100.0) * (ur_t.sq() / sr_t); // Zt (7)
@@ -101,7 +161,7 @@ impl<N: Clone + Default + Eq + core::hash::Hash> PowerStationUnit<N> {
         let zt_hv = cmplx!(rt, xt);
 
         // Power station correction factor (S3.7).
-        let ks = if oltc {
+        let ks = if self.oltc() {
             let xt = xt / (ur_t.sq() / sr_t); // Relative reactance of the transformer.
 
             (un.sq() / ur_g.sq())
@@ -109,20 +169,55 @@ impl<N: Clone + Default + Eq + core::hash::Hash> PowerStationUnit<N> {
                 * (c / (1.0 + (xdpp_pu - xt).abs() * phi.sin())) // (22)
         } else {
             ur_g = g.ur * 1e3 * (1.0 + pg); // Always use pg no OLTC.
-            (un / ur_g) * (ulv / uhv) * (c / (1.0 + xdpp_pu * phi.sin())) // (24)
-
-            /*if permanent_tap {
-                let pt = tr.p / 100.0;
-                if highest {
-                    k *= 1 - pt;
-                } else {
-                    k *= 1 + pt;
-                }
-            }*/
+
+            // (1 ± pT) for the off-load taps of the unit transformer, which are permanently
+            // set, 1 - pT for the highest partial short-circuit current on the high-voltage
+            // side. Without taps pT = 0.
+            let pt = t.tap.as_ref().map_or(0.0, |tap| tap.range());
+
+            (un / ur_g) * (ulv / uhv) * (1.0 - pt) * (c / (1.0 + xdpp_pu * phi.sin()))
+            // (24)
         };
 
-        let zs = cmplx!(ks) * (tr2 * zg + zt_hv);
+        Ok((cmplx!(ks) * tr2 * zg, cmplx!(ks) * zt_hv))
+    }
+
+    /// Corrected impedances of the generator and the unit transformer on the low-voltage side
+    /// for a short circuit between the generator and the unit transformer, using the
+    /// correction factors KG,S and KT,S or, without on-load tap-changer, KG,SO and KT,SO.
+    pub fn terminal_impedances(
+        &self,
+        peak: bool,
+        busbar_index: &BusbarIndex<N>,
+    ) -> Result<(Complex64, Complex64)> {
+        let t = &self.transformer;
+        let g = &self.generator;
+
+        let c = match busbar_index.busbar(&g.node) {
+            Some(busbar) => c_or_default(busbar),
+            None => voltage_correction_factor(g.ur, false, true),
+        };
+        let sin_phi = g.cos_phi.acos().sin();
+
+        let zt_lv = t.uncorrected_impedance(false)?;
+        let xt = zt_lv.im / ((t.ur_lv * 1e3).sq() / (t.sr * 1e3)); // Relative reactance.
+
+        let mut kgs = c / (1.0 + g.xdpp * sin_phi); // KG,S
+        let mut kts = c / (1.0 - xt * sin_phi); // KT,S
+        if !self.oltc() {
+            let pg = g.p / 100.0;
+            kgs /= 1.0 + pg; // KG,SO
+            kts /= 1.0 + pg; // KT,SO
+        }
+
+        let zg = if peak {
+            let (ur, sr) = (g.ur * 1e3, g.sr * 1e3);
+            let xdpp = g.uncorrected_impedance().im;
+            cmplx!(fictitious_resistance(ur, sr, xdpp), xdpp)
+        } else {
+            g.uncorrected_impedance()
+        };
 
-        Ok(zs)
+        Ok((cmplx!(kgs) * zg, cmplx!(kts) * zt_lv))
     }
 }
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use derive_builder::Builder;
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+use crate::busbar::{c_or_default, voltage_correction_factor, BusbarIndex};
+use crate::cmplx;
+use crate::factors::{lambda_max, mu};
+use crate::generator::{correction_factor, fictitious_resistance};
+use crate::math::SQRT_3;
+use crate::traits::Sq;
+
+/// Synchronous motor or synchronous compensator (cos_phi = 0).
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into, strip_option))]
+pub struct SynchronousMotor<N: Default> {
+    pub node: N,
+
+    /// Rated voltage of the motor (kV).
+    pub ur: f64,
+
+    /// Rated apparent power (kVA).
+    pub sr: f64,
+
+    /// Rated power factor.
+    #[builder(default = "1.0")]
+    pub cos_phi: f64,
+
+    /// Is the machine overexcited (delivering reactive power) at rated operation? The sine of
+    /// the rated power factor angle is negative for underexcited operation.
+    #[builder(default = "true")]
+    pub overexcited: bool,
+
+    /// Resistance of the synchronous machine (Ohms).
+    pub r: f64,
+
+    /// Relative subtransient reactance of the motor related to the rated impedance (p.u.).
+    pub xdpp: f64,
+
+    /// Saturated synchronous reactance (p.u.).
+    pub xdsat: f64,
+
+    /// Highest possible excitation voltage related to the excitation voltage at rated load and
+    /// power factor (p.u.).
+    #[builder(default = "1.3")]
+    pub ufmax: f64,
+}
+
+impl<N: Clone + Default + Eq + core::hash::Hash> SynchronousMotor<N> {
+    pub fn new() -> SynchronousMotorBuilder<N> {
+        SynchronousMotorBuilder::default()
+    }
+
+    /// Sine of the rated power factor angle, signed by the operating mode.
+    fn sin_phi(&self) -> f64 {
+        let sin_phi = self.cos_phi.acos().sin();
+        if self.overexcited {
+            sin_phi
+        } else {
+            -sin_phi
+        }
+    }
+
+    pub fn impedance(&self, peak: bool, busbar_index: &BusbarIndex<N>) -> Result<Complex64> {
+        let ur = self.ur * 1e3;
+        let sr = self.sr * 1e3;
+
+        let (un, c) = match busbar_index.busbar(&self.node) {
+            Some(busbar) => (busbar.un * 1e3, c_or_default(busbar)),
+            None => (ur, voltage_correction_factor(self.ur, false, true)),
+        };
+
+        let zr = ur.sq() / sr;
+        let xdpp = self.xdpp * zr; // Ohms
+
+        let km = correction_factor(un, ur, c, self.xdpp, self.sin_phi()); // (18)
+
+        let zm = if peak {
+            cmplx!(fictitious_resistance(ur, sr, xdpp), xdpp)
+        } else {
+            cmplx!(self.r, xdpp)
+        };
+
+        Ok(zm * cmplx!(km))
+    }
+
+    /// Subtransient impedance of the motor without correction factor (Ohms).
+    pub fn uncorrected_impedance(&self) -> Complex64 {
+        let ur = self.ur * 1e3;
+        let sr = self.sr * 1e3;
+
+        cmplx!(self.r, self.xdpp * ur.sq() / sr)
+    }
+
+    /// Rated current of the motor (kA).
+    pub fn ir(&self) -> f64 {
+        self.sr / (SQRT_3 * self.ur) / 1e3
+    }
+
+    /// Symmetrical short-circuit breaking current (kA) of the motor for the given partial
+    /// initial short-circuit current (kA) and minimum time delay (s).
+    pub fn breaking_current(&self, ikss: f64, tmin: f64) -> f64 {
+        mu(ikss / self.ir(), tmin) * ikss // (71)
+    }
+
+    /// Maximum steady-state short-circuit current (kA) of the motor for the given partial
+    /// initial short-circuit current (kA).
+    pub fn steady_state_current(&self, ikss: f64) -> f64 {
+        let ir = self.ir();
+
+        lambda_max(ikss / ir, self.xdpp, self.xdsat, self.sin_phi(), self.ufmax) * ir
+        // (83)
+    }
+}
@@ -0,0 +1,84 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+/// Tap changer on the high-voltage winding of a transformer.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into))]
+pub struct TapChanger {
+    /// Is the tap changer operated on load? Off-load tap changers are permanently set to
+    /// their actual position.
+    #[builder(default = "true")]
+    pub oltc: bool,
+
+    /// Voltage change per tap step in per cent of the rated voltage.
+    pub step: f64,
+
+    /// Lowest tap position.
+    pub min: i64,
+
+    /// Highest tap position.
+    pub max: i64,
+
+    /// Tap position at the rated voltage (main position).
+    pub neutral: i64,
+
+    /// Actual tap position.
+    pub position: i64,
+}
+
+/// Tap position used for the calculation.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum TapSelection {
+    /// Actual position of the tap changers.
+    Actual,
+
+    /// Main position of the on-load tap changers, at the rated transformation ratio.
+    #[default]
+    Main,
+
+    /// Highest position of the on-load tap changers.
+    Highest,
+
+    /// Lowest position of the on-load tap changers.
+    Lowest,
+}
+
+impl TapChanger {
+    pub fn new() -> TapChangerBuilder {
+        TapChangerBuilder::default()
+    }
+
+    /// Tap position for the given selection. Off-load tap changers keep their actual position.
+    pub fn select(&self, selection: TapSelection) -> i64 {
+        if !self.oltc {
+            return self.position;
+        }
+        match selection {
+            TapSelection::Actual => self.position,
+            TapSelection::Main => self.neutral,
+            TapSelection::Highest => self.max,
+            TapSelection::Lowest => self.min,
+        }
+    }
+
+    /// Voltage of the tapped winding at the given position related to its rated voltage.
+    pub fn factor(&self, position: i64) -> f64 {
+        1.0 + (position - self.neutral) as f64 * self.step / 100.0
+    }
+
+    /// Range pT of the voltage regulation of the tap changer (p.u.), the largest deviation of
+    /// the lowest or highest position from the main position.
+    pub fn range(&self) -> f64 {
+        let steps = (self.max - self.neutral).max(self.neutral - self.min);
+        steps as f64 * self.step / 100.0
+    }
+
+    /// Permanent deviation of an off-load tap changer from the main position (p.u.).
+    pub fn permanent(&self) -> f64 {
+        if self.oltc {
+            0.0
+        } else {
+            self.factor(self.position) - 1.0
+        }
+    }
+}
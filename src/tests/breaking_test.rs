@@ -0,0 +1,565 @@
+use std::f64::consts::PI;
+
+use anyhow::Result;
+use approx::assert_abs_diff_eq;
+use spsolve::rlu::RLU;
+
+use crate::factors::{dc_frequency_ratio, mu, q};
+use crate::math::SQRT_3;
+use crate::motor_group::LowVoltageMotorGroup;
+use crate::part4::iec60909_4_4;
+use crate::{assert_cmplx_eq, cmplx};
+use crate::{
+    ACSystem, Branch, Busbar, Cable, ConductorMaterial, Duty, EquipmentRating,
+    FullConverterWindUnit, Insulation, MotorCriterion, NetworkFeeder, NetworkTransformer, Waveform,
+};
+
+#[test]
+fn test_breaking_factors() {
+    assert_abs_diff_eq!(mu(2.0, 0.1), 1.0);
+    assert_abs_diff_eq!(mu(5.0, 0.1), 0.62 + 0.72 * (-1.6f64).exp(), epsilon = 1e-12);
+    assert_abs_diff_eq!(mu(5.0, 0.5), 0.56 + 0.94 * (-1.9f64).exp(), epsilon = 1e-12);
+
+    let mu_02 = mu(5.0, 0.02);
+    let mu_05 = mu(5.0, 0.05);
+    assert_abs_diff_eq!(mu(5.0, 0.035), 0.5 * (mu_02 + mu_05), epsilon = 1e-12);
+
+    assert_abs_diff_eq!(q(1.0, 0.1), 0.57, epsilon = 1e-12);
+    assert_abs_diff_eq!(q(10.0, 0.02), 1.0);
+}
+
+#[test]
+fn test_low_voltage_motor_group() -> Result<()> {
+    let group: LowVoltageMotorGroup<&str> = LowVoltageMotorGroup::new()
+        .node("M")
+        .ur(0.4)
+        .motor(0.010)
+        .motor_power(5.5)
+        .motor(0.020)
+        .motor_power(11.0)
+        .motor(0.030)
+        .motor_power(15.0)
+        .build()?;
+
+    assert_abs_diff_eq!(group.ir(), 0.060, epsilon = 1e-12);
+    assert_abs_diff_eq!(group.pr(), 31.5, epsilon = 1e-12);
+
+    let zm = 0.2 * 400.0 / (SQRT_3 * 60.0);
+    let xm = zm / (1.0 + 0.42f64.powi(2)).sqrt();
+    assert_cmplx_eq!(group.impedance()?, cmplx!(0.42 * xm, xm), epsilon = 1e-12);
+
+    // Breaking current with m = 0.05 MW.
+    let ikss = 5.0 * group.ir();
+    let ib = mu(5.0, 0.1) * (0.57 + 0.12 * 0.05f64.ln()) * ikss;
+    assert_abs_diff_eq!(group.breaking_current(ikss, 0.1), ib, epsilon = 1e-12);
+
+    Ok(())
+}
+
+#[test]
+fn test_low_voltage_motor_group_contribution() -> Result<()> {
+    let q = Busbar::new().node("Q").un(20).cmax(1.1).build()?;
+    let b = Busbar::new().node("B").un(0.4).cmax(1.05).build()?;
+    let feeder = NetworkFeeder::new()
+        .node("Q")
+        .ur(20)
+        .ikss(10)
+        .rx(0.1)
+        .build()?;
+    let t = NetworkTransformer::new()
+        .node_hv("Q")
+        .node_lv("B")
+        .sr(1000) // kVA
+        .ur_hv(20)
+        .ur_lv(0.4)
+        .urr(1) // %
+        .ukr(6) // %
+        .build()?;
+    let group = LowVoltageMotorGroup::new()
+        .node("B")
+        .ur(0.4)
+        .motor(0.200)
+        .motor_power(110.0)
+        .motor(0.300)
+        .motor_power(160.0)
+        .build()?;
+
+    let mut net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, b])
+        .feeder(feeder)
+        .transformer(t)
+        .motor_group(group)
+        .build()?;
+
+    let contributions = net.motor_contributions(RLU::default())?;
+    match contributions["Q"][0].criterion {
+        MotorCriterion::Transformers {
+            sum_prm, sum_srt, ..
+        } => {
+            // Rated active power, not the apparent power of the rated currents.
+            assert_abs_diff_eq!(sum_prm, 0.270, epsilon = 1e-12);
+            assert_abs_diff_eq!(sum_srt, 1.0, epsilon = 1e-12);
+        }
+        _ => panic!("expected transformers criterion"),
+    }
+
+    net.motor_groups[0].prm.clear();
+    assert!(net.motor_contributions(RLU::default()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn iec60909_4_4_breaking_current() -> Result<()> {
+    let tmin = 0.1;
+
+    let net = iec60909_4_4()?;
+    let solver = spsolve::rlu::RLU::default();
+
+    let faults = net.short_circuit(solver, tmin)?;
+    let fault = faults.iter().find(|f| f.node == "AT1").unwrap();
+
+    // IEC TR 60909-4, Section 4: short circuit at busbar A with the motors connected.
+    // I"kT = 14.78 kA far from generator, so IbT = I"kT.
+    // M1: I"kM1 = 2.540 kA, I"kM1/IrM1 = 4.40, mu = 0.796, m = 2.5 MW, q = 0.680.
+    // M2: I"kM2 = 2.237 kA, I"kM2/IrM2 = 6.04, mu = 0.724, m = 1.0 MW, q = 0.570.
+    let ib_m1 = 0.796 * 0.680 * 2.540;
+    let ib_m2 = 0.724 * 0.570 * 2.237;
+    assert_abs_diff_eq!(ib_m1, 1.375, epsilon = 1e-3);
+    assert_abs_diff_eq!(ib_m2, 0.923, epsilon = 1e-3);
+
+    assert_abs_diff_eq!(fault.ikss, 14.78 + 2.540 + 2.237, epsilon = 0.05);
+    assert_abs_diff_eq!(fault.ib, 14.78 + ib_m1 + ib_m2, epsilon = 0.05);
+
+    Ok(())
+}
+
+#[test]
+fn iec60909_4_4_waveform() -> Result<()> {
+    let tmin = 0.1;
+
+    let net = iec60909_4_4()?;
+    let faults = net.short_circuit(RLU::default(), tmin)?;
+    let fault = faults.iter().find(|f| f.node == "AT1").unwrap();
+
+    let waveform = net.waveform(RLU::default(), &"AT1", tmin)?;
+    assert_abs_diff_eq!(waveform.ac(0.0), fault.ikss, epsilon = 1e-12);
+    assert_abs_diff_eq!(waveform.ac(tmin), fault.ib, epsilon = 1e-12);
+    assert_abs_diff_eq!(waveform.ac(10.0), fault.ik, epsilon = 1e-6);
+
+    // (64)
+    let t = 0.05;
+    let idc = 2.0f64.sqrt() * fault.ikss * (-2.0 * PI * 50.0 * t * waveform.rx).exp();
+    assert_abs_diff_eq!(waveform.dc(t), idc, epsilon = 1e-12);
+    assert_abs_diff_eq!(waveform.dc(tmin), fault.idc, epsilon = 1e-12);
+
+    let samples = waveform.sample(0.2, 10_000.0);
+    assert_eq!(samples.len(), 2001);
+    assert_abs_diff_eq!(samples[0].1, 0.0, epsilon = 1e-12);
+
+    // The maximum of the waveform is close to the peak short-circuit current (method C).
+    let peak = samples.iter().map(|s| s.1).fold(0.0, f64::max);
+    assert_abs_diff_eq!(peak, fault.ip20, epsilon = 0.05 * fault.ip20);
+
+    Ok(())
+}
+
+#[test]
+fn test_asymmetrical_breaking_current() -> Result<()> {
+    let q = Busbar::new().node("Q").un(20).cmax(1.1).build()?;
+    let feeder = NetworkFeeder::new()
+        .node("Q")
+        .ur(20)
+        .ikss(10)
+        .rx(0.1)
+        .build()?;
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbar(q)
+        .feeder(feeder)
+        .build()?;
+
+    assert_abs_diff_eq!(dc_frequency_ratio(50.0, 0.01), 0.27);
+    assert_abs_diff_eq!(dc_frequency_ratio(50.0, 0.04), 0.15);
+    assert_abs_diff_eq!(dc_frequency_ratio(60.0, 0.05), 0.092);
+    assert_abs_diff_eq!(dc_frequency_ratio(50.0, 0.25), 0.055);
+
+    // The R/X ratio of a single feeder is independent of the equivalent frequency.
+    for tmin in [0.02, 0.05, 0.1] {
+        let fault = &net.short_circuit(RLU::default(), tmin)?[0];
+        let idc = 2.0f64.sqrt() * fault.ikss * (-2.0 * PI * 50.0 * tmin * 0.1).exp();
+        let ib_asym = (fault.ib.powi(2) + idc.powi(2)).sqrt();
+        assert_abs_diff_eq!(fault.ib_asym, ib_asym, epsilon = 1e-9);
+    }
+
+    // The d.c. component decays with the minimum time delay.
+    let faults = net.short_circuit(RLU::default(), 0.02)?;
+    let faults_late = net.short_circuit(RLU::default(), 0.1)?;
+    assert!(faults[0].ib_asym > faults_late[0].ib_asym);
+    assert!(faults_late[0].ib_asym > faults_late[0].ib);
+
+    let err = net.short_circuit(RLU::default(), 0.0).unwrap_err();
+    assert_eq!(err.to_string(), "minimum time delay must be positive");
+    assert!(net.waveform(RLU::default(), &"Q", 0.0).is_err());
+    assert!(net.evaluate_ratings(RLU::default(), -0.1, 1.0).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_waveform_breaking_current() -> Result<()> {
+    let tmin = 0.05;
+
+    let q = Busbar::new().node("Q").un(20).cmax(1.1).build()?;
+    let feeder = NetworkFeeder::new()
+        .node("Q")
+        .ur(20)
+        .ikss(10)
+        .rx(0.1)
+        .build()?;
+    let wf = FullConverterWindUnit::new()
+        .node("Q")
+        .iskwf(0.5)
+        .ikwfmax(0.5)
+        .build()?;
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbar(q)
+        .feeder(feeder)
+        .full_converter_wind_unit(wf)
+        .build()?;
+
+    let fault = &net.short_circuit(RLU::default(), tmin)?[0];
+    let waveform = net.waveform(RLU::default(), &"Q", tmin)?;
+
+    // The current source has no d.c. component and does not change its R/X ratio.
+    let ikss = 10.0 + 0.5;
+    assert_abs_diff_eq!(fault.ikss, ikss, epsilon = 1e-9);
+    assert_abs_diff_eq!(fault.rx, 0.1, epsilon = 1e-12);
+    assert_abs_diff_eq!(waveform.rx, 0.1, epsilon = 1e-12);
+    let idc = 2.0f64.sqrt() * 10.0 * (-2.0 * PI * 50.0 * tmin * 0.1).exp();
+    assert_abs_diff_eq!(fault.idc, idc, epsilon = 1e-9);
+
+    // The waveform agrees with the asymmetrical breaking current.
+    assert_abs_diff_eq!(waveform.dc(tmin), fault.idc, epsilon = 1e-12);
+    let ib_asym = (waveform.ac(tmin).powi(2) + waveform.dc(tmin).powi(2)).sqrt();
+    assert_abs_diff_eq!(fault.ib_asym, ib_asym, epsilon = 1e-12);
+
+    Ok(())
+}
+
+#[test]
+fn test_thermal_equivalent_current() {
+    let waveform = Waveform {
+        ikss: 30.0,
+        ib: 24.0,
+        ik: 15.0,
+        tmin: 0.1,
+        idc: 4.7,
+        rx: 0.07,
+        kappa: 1.8,
+        f: 50.0,
+    };
+    let tk = 0.5;
+
+    // n by numerical integration of the a.c. component.
+    let steps = 100_000;
+    let dt = tk / steps as f64;
+    let n = (0..steps)
+        .map(|i| waveform.ac((i as f64 + 0.5) * dt).powi(2) * dt)
+        .sum::<f64>()
+        / (waveform.ikss.powi(2) * tk);
+    // m = (exp(4 f Tk ln(κ - 1)) - 1) / (2 f Tk ln(κ - 1)) for κ = 1.8 (103).
+    let m = 0.089628;
+    assert_abs_diff_eq!(waveform.ith(tk), 30.0 * (m + n).sqrt(), epsilon = 1e-5);
+
+    // Far-from-generator short circuit (n = 1).
+    let waveform = Waveform {
+        ib: 30.0,
+        ik: 30.0,
+        ..waveform
+    };
+    assert_abs_diff_eq!(waveform.ith(tk), 30.0 * (m + 1.0).sqrt(), epsilon = 1e-5);
+
+    // m tends to 2 for κ = 2.
+    let waveform = Waveform {
+        kappa: 2.0,
+        ..waveform
+    };
+    assert_abs_diff_eq!(waveform.ith(tk), 30.0 * 3.0f64.sqrt(), epsilon = 1e-9);
+}
+
+#[test]
+fn test_equipment_ratings() -> Result<()> {
+    let q = Busbar::new().node("Q").un(20).cmax(1.1).build()?;
+    let feeder = NetworkFeeder::new()
+        .node("Q")
+        .ur(20)
+        .ikss(20)
+        .rx(0.1)
+        .build()?;
+    let breaker = EquipmentRating::new()
+        .node("Q")
+        .isc(25)
+        .ima(63)
+        .ithr(40)
+        .tkr(1.0)
+        .build()?;
+    let switch = EquipmentRating::new()
+        .node("Q")
+        .ima(40)
+        .ithr(20)
+        .tkr(1.0)
+        .build()?;
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbar(q)
+        .feeder(feeder)
+        .ratings([breaker, switch])
+        .build()?;
+
+    let tk = 0.5;
+    let evaluations = net.evaluate_ratings(RLU::default(), 0.1, tk)?;
+    let fault = &net.short_circuit(RLU::default(), 0.1)?[0];
+
+    // Method B, 1.15 κ limited to 2.
+    let ikss = 20.0;
+    let ip = 2.0 * 2.0f64.sqrt() * ikss;
+    // κ = 2 of method B gives m = 2.
+    let ith = ikss * 3.0f64.sqrt();
+
+    // The d.c. component at tmin is sqrt(2) 20 exp(-2 pi 50 0.1 0.1) = 1.222274 kA.
+    let ib_asym = 20.037314;
+
+    let breaker = &evaluations[0];
+    assert_abs_diff_eq!(breaker.ib, fault.ib, epsilon = 1e-9);
+    assert_abs_diff_eq!(breaker.ib_asym, ib_asym, epsilon = 1e-6);
+    assert_abs_diff_eq!(breaker.ip, ip, epsilon = 1e-6);
+    assert_abs_diff_eq!(breaker.ith, ith, epsilon = 1e-6);
+    let faults = net.thermal_short_circuit(RLU::default(), 0.1, tk)?;
+    assert_abs_diff_eq!(faults[0].ith, ith, epsilon = 1e-6);
+    assert_abs_diff_eq!(
+        breaker.breaking.unwrap(),
+        100.0 * ib_asym / 25.0,
+        epsilon = 1e-5
+    );
+    assert_abs_diff_eq!(breaker.making.unwrap(), 100.0 * ip / 63.0, epsilon = 1e-6);
+    assert_abs_diff_eq!(
+        breaker.short_time.unwrap(),
+        100.0 * ith / 40.0,
+        epsilon = 1e-6
+    );
+    assert!(breaker.violations.is_empty());
+
+    let switch = &evaluations[1];
+    assert_eq!(switch.breaking, None);
+    assert_eq!(switch.violations, vec![Duty::Making, Duty::ShortTime]);
+
+    // Ith sqrt(Tk) is compared with Ithr sqrt(Tkr) for longer durations.
+    let evaluations = net.evaluate_ratings(RLU::default(), 0.1, 2.0)?;
+    let ith = evaluations[0].ith;
+    assert_abs_diff_eq!(
+        evaluations[0].short_time.unwrap(),
+        100.0 * ith * 2.0f64.sqrt() / 40.0,
+        epsilon = 1e-9
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_branch_equipment_ratings() -> Result<()> {
+    // Cable between two busbars fed by network feeders of 10 kA and 5 kA. The shares are
+    // calculated by hand from the current divider between ZQ = 0.126387 + j1.263867 Ohms and
+    // ZB + ZL = 0.652773 + j2.727734 Ohms.
+    let q = Busbar::new().node("Q").un(20).cmax(1.1).build()?;
+    let b = Busbar::new().node("B").un(20).cmax(1.1).build()?;
+    let feeder_q = NetworkFeeder::new()
+        .node("Q")
+        .ur(20)
+        .ikss(10)
+        .rx(0.1)
+        .build()?;
+    let feeder_b = NetworkFeeder::new()
+        .node("B")
+        .ur(20)
+        .ikss(5)
+        .rx(0.1)
+        .build()?;
+    let cable = Cable::new()
+        .node_i("Q")
+        .node_j("B")
+        .l(2)
+        .rl(0.2)
+        .xl(0.1)
+        .build()?;
+    let breaker = EquipmentRating::new()
+        .node("Q")
+        .branch(Branch::Cable(0))
+        .isc(25)
+        .build()?;
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, b])
+        .feeders([feeder_q, feeder_b])
+        .cable(cable)
+        .rating(breaker)
+        .build()?;
+
+    // A short circuit on the cable side of the breaker at Q is fed by the feeder at Q.
+    let r_q = net.branch_share(RLU::default(), &"Q", Branch::Cable(0))?;
+    assert_abs_diff_eq!(r_q, 0.689648, epsilon = 1e-6);
+
+    // At B the partial current through the cable for a short circuit at B is the larger.
+    let r_b = net.branch_share(RLU::default(), &"B", Branch::Cable(0))?;
+    assert_abs_diff_eq!(r_b, 0.624633, epsilon = 1e-6);
+
+    let evaluations = net.evaluate_ratings(RLU::default(), 0.1, 0.5)?;
+    let fault = &net.short_circuit(RLU::default(), 0.1)?[0];
+    assert_eq!(evaluations[0].branch, Some(Branch::Cable(0)));
+    assert_abs_diff_eq!(evaluations[0].ib, r_q * fault.ib, epsilon = 1e-9);
+    assert_abs_diff_eq!(
+        evaluations[0].breaking.unwrap(),
+        100.0 * r_q * fault.ib_asym / 25.0,
+        epsilon = 1e-6
+    );
+
+    assert!(net
+        .branch_share(RLU::default(), &"Q", Branch::Transformer(0))
+        .is_err());
+
+    // Transformer 110/20 kV between feeders with ZQ = 0.347566 + j3.475663 Ohms at 110 kV and
+    // ZB = 0.252773 + j2.527734 Ohms at 20 kV, ZTK = 0.029245 + j1.169435 Ohms.
+    let q = Busbar::new().node("Q").un(110).cmax(1.1).build()?;
+    let a = Busbar::new().node("A").un(20).cmax(1.1).build()?;
+    let feeder_q = NetworkFeeder::new()
+        .node("Q")
+        .ur(110)
+        .ikss(20)
+        .rx(0.1)
+        .build()?;
+    let feeder_a = NetworkFeeder::new()
+        .node("A")
+        .ur(20)
+        .ikss(5)
+        .rx(0.1)
+        .build()?;
+    let t = NetworkTransformer::new()
+        .node_hv("Q")
+        .node_lv("A")
+        .ur_hv(110)
+        .ur_lv(20)
+        .sr(40_000)
+        .ukr(12)
+        .urr(0.3)
+        .build()?;
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, a])
+        .feeders([feeder_q, feeder_a])
+        .transformer(t)
+        .build()?;
+
+    let r_a = net.branch_share(RLU::default(), &"A", Branch::Transformer(0))?;
+    assert_abs_diff_eq!(r_a, 0.664428, epsilon = 1e-5);
+    let r_q = net.branch_share(RLU::default(), &"Q", Branch::Transformer(0))?;
+    assert_abs_diff_eq!(r_q, 0.969807, epsilon = 1e-5);
+
+    Ok(())
+}
+
+#[test]
+fn test_cable_thermal_check() -> Result<()> {
+    let mut cable: Cable<&str> = Cable::new()
+        .node_i("Q")
+        .node_j("B")
+        .l(2)
+        .rl(0.2)
+        .xl(0.1)
+        .qn(95)
+        .insulation(Insulation::Xlpe)
+        .build()?;
+
+    let check = cable.thermal_check(10.0, 1.0)?;
+    assert_abs_diff_eq!(check.s_min, 10_000.0 / 143.0, epsilon = 1e-9);
+    assert!(check.pass);
+
+    cable.material = ConductorMaterial::Aluminium;
+    cable.insulation = Insulation::Pvc;
+    let check = cable.thermal_check(10.0, 1.0)?;
+    assert_abs_diff_eq!(check.s_min, 10_000.0 / 76.0, epsilon = 1e-9);
+    assert!(!check.pass);
+
+    // The current is shared by the parallel cables.
+    cable.parallel = 2;
+    let check = cable.thermal_check(10.0, 1.0)?;
+    assert_abs_diff_eq!(check.s_min, 5_000.0 / 76.0, epsilon = 1e-9);
+    assert!(check.pass);
+
+    // Lower short-circuit temperature of PVC above 300 mm^2.
+    let large = Cable::<&str> {
+        qn: 400.0,
+        ..cable.clone()
+    };
+    assert_abs_diff_eq!(large.k(), 68.0);
+
+    let q = Busbar::new().node("Q").un(20).cmax(1.1).build()?;
+    let b = Busbar::new().node("B").un(20).cmax(1.1).build()?;
+    let feeder = NetworkFeeder::new()
+        .node("Q")
+        .ur(20)
+        .ikss(20)
+        .rx(0.1)
+        .build()?;
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, b])
+        .feeder(feeder)
+        .cable(cable)
+        .build()?;
+
+    // The cable is checked for the short circuit at the feeding busbar.
+    let tk = 0.5;
+    let checks = net.cable_thermal_checks(RLU::default(), 0.1, tk)?;
+    // κ = 2 of method B gives m = 2.
+    let ith = 20.0 * 3.0f64.sqrt();
+    assert_abs_diff_eq!(checks[0].ith, ith / 2.0, epsilon = 1e-6);
+    assert_abs_diff_eq!(
+        checks[0].s_min,
+        ith / 2.0 * 1e3 * tk.sqrt() / 76.0,
+        epsilon = 1e-6
+    );
+
+    // A cable to the terminals of a motor is checked at the busbar it is fed from. At B,
+    // Ik'' = 16.329967 kA with R/X = 0.359586, κ = 1.15 κ(R/X) = 1.556199 and m = 0.034093.
+    let mut net = net;
+    net.cables.push(
+        Cable::new()
+            .node_i("B")
+            .node_j("C")
+            .rl(0.2)
+            .xl(0.1)
+            .qn(95)
+            .build()?,
+    );
+    let checks = net.cable_thermal_checks(RLU::default(), 0.1, tk)?;
+    assert_abs_diff_eq!(checks[1].ith, 16.606004, epsilon = 1e-5);
+    assert_abs_diff_eq!(checks[1].k, 115.0);
+
+    net.cables.push(
+        Cable::new()
+            .node_i("C")
+            .node_j("D")
+            .rl(0.2)
+            .xl(0.1)
+            .qn(95)
+            .build()?,
+    );
+    let err = net
+        .cable_thermal_checks(RLU::default(), 0.1, tk)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "cable 3 error: no node on a busbar");
+
+    Ok(())
+}
@@ -0,0 +1,387 @@
+use anyhow::Result;
+use approx::assert_abs_diff_eq;
+use num_complex::Complex64;
+use spsolve::rlu::RLU;
+
+use crate::busbar::BusbarIndex;
+use crate::cmplx;
+use crate::math::SQRT_3;
+use crate::{
+    ACSystem, Busbar, Cable, CompensationCoil, LineCoupling, NetworkFeeder, NetworkTransformer,
+    OverheadLine, ZeroSequenceConnection,
+};
+use std::f64::consts::PI;
+
+fn feeder() -> Result<NetworkFeeder<&'static str>> {
+    Ok(NetworkFeeder::new()
+        .node("Q")
+        .ur(110)
+        .ikss(20)
+        .rx(0.1)
+        .x0x(2.0)
+        .r0x(0.2)
+        .build()?)
+}
+
+fn line(node_j: &'static str) -> Result<OverheadLine<&'static str>> {
+    Ok(OverheadLine::new()
+        .node_i("Q")
+        .node_j(node_j)
+        .l(30)
+        .rl(0.12)
+        .xl(0.39)
+        .r0(0.32)
+        .x0(1.26)
+        .build()?)
+}
+
+#[test]
+fn test_line_to_earth() -> Result<()> {
+    let q = Busbar::new().node("Q").un(110).cmax(1.1).build()?;
+    let lv = Busbar::new().node("LV").un(20).cmax(1.1).build()?;
+    let t = NetworkTransformer::new()
+        .node_hv("Q")
+        .node_lv("LV")
+        .ur_hv(110)
+        .ur_lv(20)
+        .sr(40_000)
+        .ukr(12)
+        .urr(0.3)
+        .x0x(0.95)
+        .r0r(1.0)
+        .zero_sequence(ZeroSequenceConnection::Lv)
+        .build()?;
+
+    let mut net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, lv])
+        .feeder(feeder()?)
+        .transformer(t)
+        .build()?;
+    let busbar_index = BusbarIndex::new(&net.busbars);
+
+    let zq = net.feeders[0].impedance(false, &busbar_index)?;
+    let z0q = net.feeders[0].zero_sequence_impedance(&busbar_index)?;
+    let zt = net.transformers[0].impedance(false, &busbar_index)?;
+    let z0t = net.transformers[0].zero_sequence_impedance(false, &busbar_index)?;
+    assert_abs_diff_eq!(z0q.im, 2.0 * zq.im, epsilon = 1e-9);
+
+    let faults = net.line_to_earth(RLU::default())?;
+
+    // (52)
+    let ik1 =
+        |un: f64, z1: Complex64, z0: Complex64| SQRT_3 * 1.1 * un / (cmplx!(2) * z1 + z0).norm();
+    let tr = 110.0 / 20.0;
+    assert_abs_diff_eq!(faults[0].ikss, ik1(110.0, zq, z0q), epsilon = 1e-6);
+    assert_abs_diff_eq!(
+        faults[1].ikss,
+        ik1(20.0, zt + zq / (tr * tr), z0t),
+        epsilon = 1e-6
+    );
+
+    // The delta winding blocks the zero-sequence current from the low-voltage side.
+    net.transformers[0].zero_sequence = ZeroSequenceConnection::Hv;
+    let faults = net.line_to_earth(RLU::default())?;
+    let z0 = cmplx!(1) / (cmplx!(1) / z0q + cmplx!(1) / (z0t * tr * tr));
+    assert_abs_diff_eq!(faults[0].ikss, ik1(110.0, zq, z0), epsilon = 1e-6);
+    assert!(faults[1].ikss < 1e-3);
+
+    Ok(())
+}
+
+#[test]
+fn test_line_coupling() -> Result<()> {
+    let q = Busbar::new().node("Q").un(110).cmax(1.1).build()?;
+    let b = Busbar::new()
+        .nodes(["B1", "B2"])
+        .un(110)
+        .cmax(1.1)
+        .build()?;
+
+    let coupling = LineCoupling::new()
+        .line_i(0_usize)
+        .line_j(1_usize)
+        .r0m(0.2)
+        .x0m(0.7)
+        .build()?;
+    let coupled = ACSystem::new()
+        .frequency(50)
+        .busbars([q.clone(), b.clone()])
+        .feeder(feeder()?)
+        .lines([line("B1")?, line("B2")?])
+        .coupling(coupling)
+        .build()?;
+
+    // Double circuit line with the same mutual impedance.
+    let mut double = line("B1")?;
+    double.parallel = Some(2);
+    double.r0m = 0.2;
+    double.x0m = 0.7;
+    let parallel = ACSystem::new()
+        .frequency(50)
+        .busbars([q, b])
+        .feeder(feeder()?)
+        .line(double)
+        .build()?;
+
+    let f1 = coupled.line_to_earth(RLU::default())?;
+    let f2 = parallel.line_to_earth(RLU::default())?;
+    assert_abs_diff_eq!(f1[1].ikss, f2[1].ikss, epsilon = 1e-9);
+
+    // The coupling increases the zero-sequence impedance.
+    let mut uncoupled = coupled.clone();
+    uncoupled.couplings.clear();
+    let f3 = uncoupled.line_to_earth(RLU::default())?;
+    assert!(f3[1].ikss > f1[1].ikss);
+
+    Ok(())
+}
+
+#[test]
+fn test_line_coupling_group() -> Result<()> {
+    let q = Busbar::new().node("Q").un(110).cmax(1.1).build()?;
+    let b = Busbar::new()
+        .nodes(["B1", "B2", "B3"])
+        .un(110)
+        .cmax(1.1)
+        .build()?;
+
+    // Three circuits on one tower, coupled pairwise.
+    let coupling = |i: usize, j: usize| {
+        LineCoupling::new()
+            .line_i(i)
+            .line_j(j)
+            .r0m(0.2)
+            .x0m(0.7)
+            .build()
+    };
+    let mut coupled = ACSystem::new()
+        .frequency(50)
+        .busbars([q.clone(), b.clone()])
+        .feeder(feeder()?)
+        .lines([line("B1")?, line("B2")?, line("B3")?])
+        .couplings([coupling(0, 1)?, coupling(1, 2)?, coupling(0, 2)?])
+        .build()?;
+
+    // Triple circuit line with the same mutual impedance.
+    let mut triple = line("B1")?;
+    triple.parallel = Some(3);
+    triple.r0m = 0.2;
+    triple.x0m = 0.7;
+    let parallel = ACSystem::new()
+        .frequency(50)
+        .busbars([q, b])
+        .feeder(feeder()?)
+        .line(triple)
+        .build()?;
+
+    let f1 = coupled.line_to_earth(RLU::default())?;
+    let f2 = parallel.line_to_earth(RLU::default())?;
+    assert_abs_diff_eq!(f1[1].ikss, f2[1].ikss, epsilon = 1e-9);
+
+    // A pair may only be coupled once.
+    coupled.couplings.push(coupling(2, 1)?);
+    let err = coupled.line_to_earth(RLU::default()).unwrap_err();
+    assert_eq!(err.to_string(), "coupling 4 error: lines already coupled");
+
+    Ok(())
+}
+
+#[test]
+fn test_capacitive_earth_fault() -> Result<()> {
+    let q = Busbar::new().node("Q").un(110).cmax(1.1).build()?;
+    let a = Busbar::new().node("A").un(20).cmax(1.1).build()?;
+    let b = Busbar::new().node("B").un(20).cmax(1.1).build()?;
+    let c = Busbar::new().node("C").un(20).cmax(1.1).build()?;
+    let t = NetworkTransformer::new()
+        .node_hv("Q")
+        .node_lv("A")
+        .ur_hv(110)
+        .ur_lv(20)
+        .sr(40_000)
+        .ukr(12)
+        .urr(0.3)
+        .build()?;
+    let cable = Cable::new()
+        .node_i("A")
+        .node_j("B")
+        .l(10)
+        .rl(0.2)
+        .xl(0.1)
+        .c1(0.3)
+        .c0(0.3)
+        .parallel(2)
+        .build()?;
+    let line: OverheadLine<&str> = OverheadLine::new()
+        .node_i("A")
+        .node_j("C")
+        .l(20)
+        .rl(0.3)
+        .xl(0.35)
+        .c1(0.009)
+        .c0(0.005)
+        .build()?;
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, a, b, c])
+        .feeder(feeder()?)
+        .transformer(t)
+        .cable(cable)
+        .line(line)
+        .build()?;
+
+    let c0 = 0.3 * 10.0 * 2.0 + 0.005 * 20.0;
+    let ice = SQRT_3 * 2.0 * PI * 50.0 * c0 * 1e-6 * 1.1 * 20.0;
+
+    let faults = net.capacitive_earth_faults(None);
+    assert_eq!(faults[0].ice, 0.0);
+    for f in &faults[1..] {
+        assert_abs_diff_eq!(f.c0, c0, epsilon = 1e-12);
+        assert_abs_diff_eq!(f.ice, ice, epsilon = 1e-12);
+        assert_abs_diff_eq!(f.ires, ice, epsilon = 1e-12);
+    }
+
+    let coil = CompensationCoil::new().v(0.1).d(0.04).build()?;
+    let faults = net.capacitive_earth_faults(Some(&coil));
+    assert_abs_diff_eq!(faults[1].il, 1.1 * ice, epsilon = 1e-12);
+    assert_abs_diff_eq!(faults[1].ires, ice * 0.0116f64.sqrt(), epsilon = 1e-12);
+
+    Ok(())
+}
+
+#[test]
+fn test_double_earth_fault() -> Result<()> {
+    let q = Busbar::new().node("Q").un(110).cmax(1.1).build()?;
+    let a = Busbar::new().node("A").un(20).cmax(1.1).build()?;
+    let b = Busbar::new().node("B").un(20).cmax(1.1).build()?;
+    let c = Busbar::new().node("C").un(20).cmax(1.1).build()?;
+    let t = NetworkTransformer::new()
+        .node_hv("Q")
+        .node_lv("A")
+        .ur_hv(110)
+        .ur_lv(20)
+        .sr(40_000)
+        .ukr(12)
+        .urr(0.3)
+        .x0x(1.0)
+        .r0r(1.0)
+        .zero_sequence(ZeroSequenceConnection::Hv)
+        .build()?;
+    let line: OverheadLine<&str> = OverheadLine::new()
+        .node_i("A")
+        .node_j("B")
+        .l(10)
+        .rl(0.3)
+        .xl(0.35)
+        .r0(0.45)
+        .x0(1.2)
+        .build()?;
+    let cable = Cable::new()
+        .node_i("B")
+        .node_j("C")
+        .l(2)
+        .rl(0.2)
+        .xl(0.1)
+        .r0(0.8)
+        .x0(0.4)
+        .build()?;
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, a, b, c])
+        .feeder(feeder()?)
+        .transformer(t)
+        .line(line)
+        .cable(cable)
+        .build()?;
+    let busbar_index = BusbarIndex::new(&net.busbars);
+
+    let tr = 110.0 / 20.0;
+    let zs = net.transformers[0].impedance(false, &busbar_index)?
+        + net.feeders[0].impedance(false, &busbar_index)? / (tr * tr);
+    let z1l = net.lines[0].impedance(50.0)?;
+    let z0l = net.lines[0].zero_sequence_impedance(50.0)?;
+
+    let fault = net.double_earth_fault(RLU::default(), &"A", &"B")?;
+    let ikee = 3.0 * 1.1 * 20.0 / (cmplx!(6) * zs + cmplx!(2) * z1l + z0l).norm();
+    assert_abs_diff_eq!(fault.ikee, ikee, epsilon = 1e-6);
+
+    // The zero-sequence current flows through the line between the fault locations.
+    assert_abs_diff_eq!(fault.lines[0].ik1, ikee, epsilon = 1e-6);
+    assert_abs_diff_eq!(fault.lines[0].ie, ikee, epsilon = 1e-6);
+    assert_abs_diff_eq!(fault.cables[0].ik1, 0.0, epsilon = 1e-6);
+
+    assert!(net.double_earth_fault(RLU::default(), &"A", &"Q").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_double_earth_fault_parallel_paths() -> Result<()> {
+    // Isolated 20 kV network fed from 110 kV through a YNd transformer, with an overhead line
+    // and a cable in parallel between the fault locations A and B. The reference values are
+    // calculated by hand following IEC 60909-3, clause 4:
+    //
+    //   ZQt = 0.011490 + j0.114897 Ohms (referred with tr = 5.5)
+    //   KT = 0.974834, ZTK = 0.029245 + j1.169435 Ohms
+    //   Z(1)L || Z(1)C = 0.405457 + j0.329118 Ohms
+    //   Z(0)L || Z(0)C = 1.449106 + j1.316874 Ohms
+    //   I"kEE = 3 c Un / |6 (ZQt + ZTK) + 2 Z(1)L||C + Z(0)L||C| = 6.600 kA
+    let q = Busbar::new().node("Q").un(110).cmax(1.1).build()?;
+    let a = Busbar::new().node("A").un(20).cmax(1.1).build()?;
+    let b = Busbar::new().node("B").un(20).cmax(1.1).build()?;
+    let t = NetworkTransformer::new()
+        .node_hv("Q")
+        .node_lv("A")
+        .ur_hv(110)
+        .ur_lv(20)
+        .sr(40_000)
+        .ukr(12)
+        .urr(0.3)
+        .x0x(1.0)
+        .r0r(1.0)
+        .zero_sequence(ZeroSequenceConnection::Hv)
+        .build()?;
+    let line: OverheadLine<&str> = OverheadLine::new()
+        .node_i("A")
+        .node_j("B")
+        .l(10)
+        .rl(0.12)
+        .xl(0.39)
+        .r0(0.35)
+        .x0(1.2)
+        .build()?;
+    let cable = Cable::new()
+        .node_i("A")
+        .node_j("B")
+        .l(3)
+        .rl(0.16)
+        .xl(0.11)
+        .r0(0.6)
+        .x0(0.45)
+        .build()?;
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, a, b])
+        .feeder(feeder()?)
+        .transformer(t)
+        .line(line)
+        .cable(cable)
+        .build()?;
+
+    let fault = net.double_earth_fault(RLU::default(), &"A", &"B")?;
+    assert_abs_diff_eq!(fault.ikee, 6.6001, epsilon = 1e-4);
+
+    // 3I0 splits between the line and the cable in the ratio of their zero-sequence
+    // impedances: I"kEE |Z(0)C / (Z(0)L + Z(0)C)| and I"kEE |Z(0)L / (Z(0)L + Z(0)C)|.
+    assert_abs_diff_eq!(fault.lines[0].ik1, 1.0339, epsilon = 1e-4);
+    assert_abs_diff_eq!(fault.cables[0].ik1, 5.7438, epsilon = 1e-4);
+
+    // Without earth wires or sheath all of the current returns through earth.
+    assert_abs_diff_eq!(fault.lines[0].ie, fault.lines[0].ik1, epsilon = 1e-9);
+    assert_abs_diff_eq!(fault.cables[0].iq, 0.0, epsilon = 1e-9);
+
+    Ok(())
+}
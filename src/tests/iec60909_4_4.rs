@@ -1,6 +1,7 @@
 use anyhow::Result;
 
 use crate::busbar::BusbarIndex;
+use crate::contribution::{transformer_limit, MotorCriterion};
 use crate::part4::iec60909_4_4;
 use crate::{assert_cmplx_eq, cmplx};
 
@@ -9,7 +10,7 @@ fn iec60909_4_4_feeder() -> Result<()> {
     let z_qt = cmplx!(0.0058, 0.0579);
 
     let net = iec60909_4_4()?;
-    let index = &BusbarIndex::new(&net.busbars);
+    let index = BusbarIndex::new(&net.busbars);
 
     let z = net.feeders[0].impedance(false, &index)?;
 
@@ -92,3 +93,44 @@ fn iec60909_4_4_motor() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn iec60909_4_4_motor_contributions() -> Result<()> {
+    let net = iec60909_4_4()?;
+    let solver = spsolve::rlu::RLU::default();
+
+    let contributions = net.motor_contributions(solver)?;
+
+    // Short circuit at busbar A: the motors are connected directly.
+    let a = &contributions["AT1"];
+    assert_eq!(a.len(), 1);
+    assert_eq!(a[0].motors, vec![0, 1]);
+    assert!(a[0].included);
+    match a[0].criterion {
+        MotorCriterion::RatedCurrent { sum_irm, ikss } => {
+            approx::assert_abs_diff_eq!(sum_irm, 0.577 + 0.370, epsilon = 1e-3);
+            approx::assert_abs_diff_eq!(ikss, 14.78, epsilon = 1e-2);
+        }
+        _ => panic!("expected rated current criterion"),
+    }
+
+    // Short circuit at busbar Q: the motors are fed through T1 and T2.
+    let q = &contributions["Q1"];
+    assert_eq!(q.len(), 1);
+    assert!(q[0].included);
+    match q[0].criterion {
+        MotorCriterion::Transformers {
+            sum_prm,
+            sum_srt,
+            limit,
+        } => {
+            approx::assert_abs_diff_eq!(sum_prm, 8.0, epsilon = 1e-9);
+            approx::assert_abs_diff_eq!(sum_srt, 30.0, epsilon = 1e-9);
+            let limit_q = transformer_limit(1.1, 30.0, 33.0, 13.12);
+            approx::assert_abs_diff_eq!(limit, limit_q, epsilon = 1e-3);
+        }
+        _ => panic!("expected transformers criterion"),
+    }
+
+    Ok(())
+}
@@ -42,7 +42,7 @@ fn iec60909_4_5_power_station() -> Result<()> {
     let s = &net.power_stations[0];
     let busbar_index = BusbarIndex::new(&net.busbars);
 
-    let z = s.impedance(true, true, false, &busbar_index)?;
+    let z = s.impedance(true, false, &busbar_index)?;
     assert_cmplx_eq!(z, zs, epsilon = 1e-3);
 
     Ok(())
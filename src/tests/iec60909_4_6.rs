@@ -1,9 +1,10 @@
 use anyhow::Result;
 
-use crate::busbar::BusbarIndex;
+use crate::busbar::{c_or_default, BusbarIndex};
 use crate::part4::iec60909_4_6;
 use crate::transformer3::TransformerSide;
-use crate::{assert_cmplx_eq, cmplx};
+use crate::{assert_cmplx_eq, cmplx, PerUnit};
+use spsolve::rlu::RLU;
 
 #[test]
 fn iec60909_4_6_feeder() -> Result<()> {
@@ -87,10 +88,10 @@ fn iec60909_4_6_power_station() -> Result<()> {
     let s2 = &net.power_stations[1];
     let busbar_index = BusbarIndex::new(&net.busbars);
 
-    let z1 = s1.impedance(true, true, false, &busbar_index)?;
+    let z1 = s1.impedance(true, false, &busbar_index)?;
     assert_cmplx_eq!(z1, z_s1, epsilon = tol);
 
-    let z2 = s2.impedance(true, false, false, &busbar_index)?;
+    let z2 = s2.impedance(true, false, &busbar_index)?;
     assert_cmplx_eq!(z2, z_s2, epsilon = tol);
 
     Ok(())
@@ -178,3 +179,103 @@ fn iec60909_4_6_line() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn iec60909_4_6_short_circuit() -> Result<()> {
+    // Initial symmetrical short-circuit currents (kA) at the busbars.
+    let ikss = [
+        ("1-T3", 40.6447),
+        ("2-T3", 31.7830),
+        ("3-T2", 19.6730),
+        ("4-T1", 16.2277),
+        ("5-L4", 33.1894),
+        ("6-G3", 37.5629),
+        ("7-M1", 25.5895),
+        ("8", 13.5778),
+    ];
+
+    let net = iec60909_4_6()?;
+
+    // The feeder is referred to the connection point at 380 kV, the ratio used to refer it
+    // by hand is not used by the system.
+    let faults = net.short_circuit(RLU::default(), 0.1)?;
+
+    for (node, ikss) in ikss {
+        let fault = faults.iter().find(|f| f.node == node).unwrap();
+        approx::assert_abs_diff_eq!(fault.ikss, ikss, epsilon = 1e-3);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn iec60909_4_6_per_unit() -> Result<()> {
+    let net = iec60909_4_6()?;
+    let base = PerUnit::new().sbase(100).build()?;
+
+    let zk = net.fault_impedance(RLU::default())?;
+    let zk_pu = net.fault_impedance_pu(RLU::default(), &base)?;
+    let faults = net.short_circuit(RLU::default(), 0.1)?;
+    let faults_pu = net.short_circuit_pu(RLU::default(), 0.1, &base)?;
+    assert_eq!(faults_pu.len(), faults.len());
+
+    for busbar in &net.busbars {
+        let node = &busbar.nodes[0];
+
+        // The per-unit calculation matches the calculation in Ohms.
+        let z = base.impedance(zk[node], busbar.un);
+        assert_cmplx_eq!(zk_pu[node], z, epsilon = 1e-9);
+
+        let fault = faults.iter().find(|f| f.node == *node).unwrap();
+        let fault_pu = faults_pu.iter().find(|f| f.node == *node).unwrap();
+        assert_eq!(*fault_pu, base.fault(fault, busbar.un));
+        let ikss_pu = c_or_default(busbar) / zk_pu[node].norm();
+        approx::assert_abs_diff_eq!(fault_pu.ikss, ikss_pu, epsilon = 1e-9);
+        approx::assert_abs_diff_eq!(
+            fault_pu.ip50 * base.ibase(busbar.un),
+            fault.ip50,
+            epsilon = 1e-9
+        );
+        approx::assert_abs_diff_eq!(
+            fault_pu.ikss * base.ibase(busbar.un),
+            fault.ikss,
+            epsilon = 1e-9
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn iec60909_4_6_neglect_motors() -> Result<()> {
+    let mut net = iec60909_4_6()?;
+    net.neglect_motors = true;
+
+    let contributions = net.motor_contributions(RLU::default())?;
+    let faults = net.short_circuit(RLU::default(), 0.1)?;
+
+    let mut without_motors = net.clone();
+    without_motors.motors.clear();
+    let faults_wo = without_motors.short_circuit(RLU::default(), 0.1)?;
+
+    let reference = iec60909_4_6()?.short_circuit(RLU::default(), 0.1)?;
+
+    let mut neglected = 0;
+    for (i, fault) in faults.iter().enumerate() {
+        let Some(groups) = contributions.get(&fault.node) else {
+            continue;
+        };
+        if groups.iter().all(|g| !g.included) {
+            // The motors are left out of the admittance matrix at this location.
+            approx::assert_abs_diff_eq!(fault.ikss, faults_wo[i].ikss, epsilon = 1e-9);
+            approx::assert_abs_diff_eq!(fault.ib, faults_wo[i].ib, epsilon = 1e-9);
+            assert!(fault.ikss < reference[i].ikss);
+            neglected += 1;
+        } else if groups.iter().all(|g| g.included) {
+            approx::assert_abs_diff_eq!(fault.ikss, reference[i].ikss, epsilon = 1e-9);
+        }
+    }
+    assert!(neglected > 0);
+
+    Ok(())
+}
@@ -1,7 +1,19 @@
+use std::f64::consts::PI;
+
 use anyhow::Result;
+use approx::assert_abs_diff_eq;
 use num_complex::Complex64;
+use spsolve::rlu::RLU;
 
+use crate::busbar::BusbarIndex;
+use crate::cmplx;
+use crate::math::SQRT_3;
 use crate::{assert_cmplx_eq, part4::iec60909_4_3};
+use crate::{
+    ACSystem, Busbar, Cable, CableSheath, Conductor, EarthReturn, Load, NetworkFeeder,
+    NetworkTransformer, OperatingPoint, OverheadLine, PowerStationUnit, SeriesCapacitor,
+    SynchronousGenerator, TapChanger, TapSelection, TowerGeometry,
+};
 
 #[test]
 fn test_fault_impedance() -> Result<()> {
@@ -11,7 +23,7 @@ fn test_fault_impedance() -> Result<()> {
     };
 
     let net = iec60909_4_3()?;
-    let solver = spsolve::rlu::RLU::default();
+    let solver = RLU::default();
 
     let zk = net.fault_impedance(solver)?;
 
@@ -19,3 +31,428 @@ fn test_fault_impedance() -> Result<()> {
 
     Ok(())
 }
+
+fn transformer() -> Result<NetworkTransformer<&'static str>> {
+    Ok(NetworkTransformer::new()
+        .node_hv("HV")
+        .node_lv("LV")
+        .ur_hv(110)
+        .ur_lv(20)
+        .sr(40_000)
+        .ukr(12)
+        .urr(0.3)
+        .build()?)
+}
+
+#[test]
+fn test_transformer_operating_conditions() -> Result<()> {
+    let hv = Busbar::new().node("HV").un(110).cmax(1.1).build()?;
+    let lv = Busbar::new().node("LV").un(20).cmax(1.1).build()?;
+    let busbars = [hv, lv];
+    let busbar_index = BusbarIndex::new(&busbars);
+
+    let mut t = transformer()?;
+    t.ub = Some(115.0);
+    t.ib = 0.18;
+    t.phib = 0.9f64.acos();
+
+    // KT = 1.00703 (12b)
+    assert_cmplx_eq!(
+        t.impedance(true, &busbar_index)?,
+        cmplx!(0.913877, 36.543651),
+        epsilon = 1e-6
+    );
+    assert_cmplx_eq!(
+        t.impedance(false, &busbar_index)?,
+        cmplx!(0.030211, 1.208055),
+        epsilon = 1e-6
+    );
+
+    // Without load the factor only accounts for the operating voltage.
+    t.ib = 0.0;
+    let kt = (110.0 / 115.0) * 1.1;
+    assert_cmplx_eq!(
+        t.impedance(true, &busbar_index)?,
+        t.uncorrected_impedance(true)? * kt,
+        epsilon = 1e-9
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_multi_voltage_fault_impedance() -> Result<()> {
+    let hv = Busbar::new().node("HV").un(110).cmax(1.1).build()?;
+    let lv = Busbar::new().node("LV").un(20).cmax(1.1).build()?;
+    let feeder = NetworkFeeder::new()
+        .node("HV")
+        .ur(110)
+        .ikss(20)
+        .rx(0.1)
+        .build()?;
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbars([hv, lv])
+        .feeder(feeder)
+        .transformer(transformer()?)
+        .build()?;
+    let busbar_index = BusbarIndex::new(&net.busbars);
+
+    // Each node is referred to its own nominal voltage.
+    let zq = net.feeders[0].impedance(false, &busbar_index)?;
+    let zt = net.transformers[0].impedance(false, &busbar_index)?;
+    let tr = 110.0 / 20.0;
+
+    let zk = net.fault_impedance(RLU::default())?;
+    assert_cmplx_eq!(zk["HV"], zq, epsilon = 1e-9);
+    assert_cmplx_eq!(zk["LV"], zt + zq / (tr * tr), epsilon = 1e-9);
+
+    let faults = net.short_circuit(RLU::default(), 0.1)?;
+    assert_abs_diff_eq!(faults[0].ikss, 20.0, epsilon = 1e-9);
+    assert_abs_diff_eq!(
+        faults[1].ikss,
+        1.1 * 20.0 / SQRT_3 / (zt + zq / (tr * tr)).norm(),
+        epsilon = 1e-9
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_transformer_operating_conditions_load_flow() -> Result<()> {
+    let hv = Busbar::new().node("HV").un(110).cmax(1.1).build()?;
+    let lv = Busbar::new().node("LV").un(20).cmax(1.1).build()?;
+    let q = NetworkFeeder::new()
+        .node("HV")
+        .ur(110)
+        .ikss(20)
+        .rx(0.1)
+        .build()?;
+    let load = Load::new().node("LV").p(30).q(10).build()?;
+
+    let mut net = ACSystem::new()
+        .frequency(50)
+        .busbars([hv, lv])
+        .feeder(q)
+        .transformer(transformer()?)
+        .load(load)
+        .build()?;
+
+    let op = OperatingPoint::new().uq(1.05).build()?;
+    net.set_transformer_operating_conditions(RLU::default(), &op)?;
+    let v = net.load_flow(RLU::default(), &op)?;
+
+    let t = &net.transformers[0];
+    let ub = t.ub.unwrap();
+    assert_abs_diff_eq!(ub, v["HV"].norm(), epsilon = 1e-9);
+    assert!(ub < 1.05 * 110.0);
+
+    // Load and transformer losses supplied from the high-voltage side.
+    let i_lv = t.ib * 110.0 / 20.0;
+    let zt = t.uncorrected_impedance(false)?;
+    let s = cmplx!(30, 10) + zt * 3.0 * i_lv * i_lv;
+    let s_hv = Complex64::from_polar(SQRT_3 * ub * t.ib, t.phib);
+    assert_cmplx_eq!(s_hv, s, epsilon = 1e-6);
+
+    Ok(())
+}
+
+#[test]
+fn test_tap_changer() -> Result<()> {
+    let hv = Busbar::new().node("HV").un(110).cmax(1.1).build()?;
+    let lv = Busbar::new().node("LV").un(20).cmax(1.1).build()?;
+    let busbars = [hv, lv];
+    let busbar_index = BusbarIndex::new(&busbars);
+
+    let t0 = transformer()?;
+
+    let mut t = transformer()?;
+    t.tap = Some(
+        TapChanger::new()
+            .step(1.5)
+            .min(-8)
+            .max(8)
+            .position(3)
+            .build()?,
+    );
+    assert_abs_diff_eq!(t.ratio(TapSelection::Actual), 110.0 * 1.045 / 20.0);
+    assert_abs_diff_eq!(t.ratio(TapSelection::Highest), 110.0 * 1.12 / 20.0);
+    assert_abs_diff_eq!(t.ratio(TapSelection::Lowest), 110.0 * 0.88 / 20.0);
+    assert_abs_diff_eq!(t.tap.as_ref().unwrap().range(), 0.12, epsilon = 1e-12);
+
+    // The impedance of a transformer with on-load tap-changer is related to the main position.
+    assert_eq!(
+        t.impedance(true, &busbar_index)?,
+        t0.impedance(true, &busbar_index)?
+    );
+
+    // Off-load tap changer permanently set above the main position.
+    t.tap = Some(
+        TapChanger::new()
+            .oltc(false)
+            .step(2.5)
+            .min(-2)
+            .max(2)
+            .position(2)
+            .build()?,
+    );
+    assert_abs_diff_eq!(t.ur_hv_tap(), 115.5, epsilon = 1e-12);
+    assert_abs_diff_eq!(
+        t.ratio(TapSelection::Highest),
+        115.5 / 20.0,
+        epsilon = 1e-12
+    );
+    assert_abs_diff_eq!(t.ratio(TapSelection::Lowest), 115.5 / 20.0, epsilon = 1e-12);
+    assert_cmplx_eq!(
+        t.impedance(true, &busbar_index)?,
+        t0.impedance(true, &busbar_index)? * 1.05 * 1.05,
+        epsilon = 1e-9
+    );
+    assert_eq!(
+        t.impedance(false, &busbar_index)?,
+        t0.impedance(false, &busbar_index)?
+    );
+
+    // KSO of a power station unit with off-load taps of ±2 x 2.5% (24), with 1 - pT for the
+    // maximum current independent of the tap in use.
+    let g = SynchronousGenerator::new()
+        .node("LV")
+        .ur(21)
+        .sr(50_000)
+        .cos_phi(0.85)
+        .r(0.04)
+        .xdpp(0.16)
+        .build()?;
+    let mut t = transformer()?;
+    t.ur_lv = 21.0;
+    let s0 = PowerStationUnit::new()
+        .generator(g.clone())
+        .transformer(t.clone())
+        .build()?;
+    t.tap = Some(
+        TapChanger::new()
+            .oltc(false)
+            .step(2.5)
+            .min(-2)
+            .max(2)
+            .position(-1)
+            .build()?,
+    );
+    let s = PowerStationUnit::new()
+        .generator(g)
+        .transformer(t)
+        .build()?;
+    assert_cmplx_eq!(
+        s.impedance(true, false, &busbar_index)?,
+        s0.impedance(true, false, &busbar_index)? * 0.95,
+        epsilon = 1e-9
+    );
+
+    // The correction factor KS is used with an on-load tap-changer.
+    assert!(!s0.oltc() && !s.oltc());
+    let mut s = s;
+    if let Some(tap) = s.transformer.tap.as_mut() {
+        tap.oltc = true;
+    }
+    assert!(s.oltc());
+    assert!(s.impedance(true, false, &busbar_index)? != s0.impedance(true, false, &busbar_index)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_tap_selection() -> Result<()> {
+    let hv = Busbar::new().node("HV").un(110).cmax(1.1).build()?;
+    let lv = Busbar::new().node("LV").un(20).cmax(1.1).build()?;
+    let feeder = NetworkFeeder::new()
+        .node("HV")
+        .ur(110)
+        .ikss(20)
+        .rx(0.1)
+        .build()?;
+    let mut t = transformer()?;
+    t.tap = Some(
+        TapChanger::new()
+            .step(1.5)
+            .min(-8)
+            .max(8)
+            .position(3)
+            .build()?,
+    );
+
+    let mut net = ACSystem::new()
+        .frequency(50)
+        .busbars([hv, lv])
+        .feeder(feeder)
+        .transformer(t)
+        .build()?;
+    assert_eq!(net.taps, TapSelection::Main);
+    let busbar_index = BusbarIndex::new(&net.busbars);
+
+    let zq = net.feeders[0].impedance(false, &busbar_index)?;
+    let zt = net.transformers[0].impedance(false, &busbar_index)?;
+    let ikss = |n: f64| 1.1 * 20.0 / SQRT_3 / (zt + zq / (n * n)).norm();
+
+    let mut ikss_lv = vec![];
+    for (taps, n) in [
+        (TapSelection::Lowest, 110.0 * 0.88 / 20.0),
+        (TapSelection::Main, 110.0 / 20.0),
+        (TapSelection::Actual, 110.0 * 1.045 / 20.0),
+        (TapSelection::Highest, 110.0 * 1.12 / 20.0),
+    ] {
+        net.taps = taps;
+        let faults = net.short_circuit(RLU::default(), 0.1)?;
+        assert_abs_diff_eq!(faults[0].ikss, 20.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(faults[1].ikss, ikss(n), epsilon = 1e-9);
+        ikss_lv.push(faults[1].ikss);
+    }
+
+    // The network impedance referred to the low-voltage side decreases with the ratio.
+    assert!(ikss_lv.windows(2).all(|w| w[0] < w[1]));
+
+    Ok(())
+}
+
+#[test]
+fn test_line_geometry() -> Result<()> {
+    let f = 50.0;
+    let phase = |x: f64| Conductor::new().x(x).y(20).r(0.12).radius(0.0109).build();
+    let earth_wire = Conductor::new().x(0).y(28).r(0.3).radius(0.008).build()?;
+
+    let mut geometry = TowerGeometry::new()
+        .phases([phase(-5.0)?, phase(0.0)?, phase(5.0)?])
+        .build()?;
+
+    let xk = 2.0 * PI * f * 2e-4; // Ohms/km
+    let re = PI * PI * f * 1e-4; // Ohms/km
+    let delta = 1.851 / (2.0 * PI * f * 4.0 * PI * 1e-7 / 100.0).sqrt();
+    let d = (5.0f64 * 5.0 * 10.0).cbrt();
+    let r = 0.0109;
+
+    let z1 = cmplx!(0.12, xk * (0.25 + (d / r).ln()));
+    let z0 = cmplx!(
+        0.12 + 3.0 * re,
+        xk * (0.25 + 3.0 * (delta / (r * d * d).cbrt()).ln())
+    );
+
+    let (z1_g, z0_g) = geometry.sequence_impedances(f)?;
+    assert_cmplx_eq!(z1_g, z1, epsilon = 1e-9);
+    assert_cmplx_eq!(z0_g, z0, epsilon = 1e-9);
+
+    // The earth wire reduces the zero-sequence impedance.
+    geometry.earth_wires.push(earth_wire);
+
+    let d_lq = (8.0f64.hypot(5.0) * 8.0 * 8.0f64.hypot(5.0)).cbrt();
+    let z_lq = cmplx!(re, xk * (delta / d_lq).ln());
+    let z_q = cmplx!(0.3 + re, xk * (0.25 + (delta / 0.008).ln()));
+
+    let (_, z0_g) = geometry.sequence_impedances(f)?;
+    assert_cmplx_eq!(z0_g, z0 - 3.0 * z_lq * z_lq / z_q, epsilon = 1e-9);
+
+    let line: OverheadLine<&str> = OverheadLine::new()
+        .node_i("A")
+        .node_j("B")
+        .l(20)
+        .geometry(geometry)
+        .build()?;
+    assert_cmplx_eq!(
+        line.zero_sequence_impedance(f)?,
+        z0_g * 20.0,
+        epsilon = 1e-9
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_reduction_factor() -> Result<()> {
+    let f = 50.0;
+    let xk = 2.0 * PI * f * 2e-4; // Ohms/km
+    let re = PI * PI * f * 1e-4; // Ohms/km
+    let delta = 1.851 / (2.0 * PI * f * 4.0 * PI * 1e-7 / 100.0).sqrt();
+
+    let phase = |x: f64| Conductor::new().x(x).y(20).r(0.12).radius(0.0109).build();
+    let earth_wire = |x: f64| Conductor::new().x(x).y(28).r(0.3).radius(0.008).build();
+
+    // Two earth wires.
+    let geometry = TowerGeometry::new()
+        .phases([phase(-5.0)?, phase(0.0)?, phase(5.0)?])
+        .earth_wires([earth_wire(-4.0)?, earth_wire(4.0)?])
+        .build()?;
+    let line: OverheadLine<&str> = OverheadLine::new().geometry(geometry).build()?;
+
+    let d_lq = (1.0f64.hypot(8.0) * 4.0f64.hypot(8.0) * 9.0f64.hypot(8.0)).cbrt();
+    let z_lq = cmplx!(re, xk * (delta / d_lq).ln());
+    let z_q2 = cmplx!(
+        0.15 + re,
+        xk * (0.125 + (delta / (0.008f64 * 8.0).sqrt()).ln())
+    );
+    let r = cmplx!(1) - z_lq / z_q2;
+
+    assert_cmplx_eq!(line.reduction_factor(f)?, r, epsilon = 1e-9);
+
+    let split = EarthReturn::new(10.0, r);
+    assert_abs_diff_eq!(split.ie, 10.0 * r.norm(), epsilon = 1e-12);
+    assert_abs_diff_eq!(split.iq, 10.0 * (cmplx!(1) - r).norm(), epsilon = 1e-12);
+    assert!(split.ie < 10.0 && split.iq < 10.0);
+
+    // Three single-core cables in trefoil.
+    let sheath = CableSheath::new().r(0.5).radius(0.03).d(0.07).build()?;
+    let cable: Cable<&str> = Cable::new().sheath(sheath).build()?;
+
+    let z_ls = cmplx!(re, xk * (delta / (0.03f64 * 0.07 * 0.07).cbrt()).ln());
+    let r = cmplx!(0.5 / 3.0) / (cmplx!(0.5 / 3.0) + z_ls);
+    assert_cmplx_eq!(cable.reduction_factor(f), r, epsilon = 1e-9);
+
+    Ok(())
+}
+
+#[test]
+fn test_series_capacitor() -> Result<()> {
+    let q = Busbar::new().node("Q").un(220).build()?;
+    let a = Busbar::new().node("A").un(220).build()?;
+    let b = Busbar::new().node("B").un(220).build()?;
+    let feeder = NetworkFeeder::new()
+        .node("Q")
+        .ur(220)
+        .ikss(20)
+        .rx(0.1)
+        .build()?;
+    let line = OverheadLine::new()
+        .node_i("Q")
+        .node_j("A")
+        .l(150)
+        .rl(0.05)
+        .xl(0.4)
+        .build()?;
+    let capacitor = SeriesCapacitor::new()
+        .node_i("A")
+        .node_j("B")
+        .xc(20)
+        .upl(90)
+        .build()?;
+
+    let mut net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, a, b])
+        .feeder(feeder)
+        .line(line)
+        .series_capacitor(capacitor)
+        .build()?;
+
+    // The capacitor is bypassed by the varistor.
+    let zk = net.fault_impedance(RLU::default())?;
+    assert_cmplx_eq!(zk["B"], zk["A"], epsilon = 1e-5);
+
+    // Without protection the capacitor compensates part of the line reactance.
+    net.series_capacitors[0].upl = None;
+    let zk = net.fault_impedance(RLU::default())?;
+    assert_cmplx_eq!(zk["B"], zk["A"] + cmplx!(0, -20), epsilon = 1e-9);
+
+    assert!(!net.series_capacitors[0].neglected());
+    net.series_capacitors[0].bypass = true;
+    assert!(net.series_capacitors[0].neglected());
+
+    Ok(())
+}
@@ -0,0 +1,587 @@
+use anyhow::Result;
+use approx::assert_abs_diff_eq;
+
+use crate::busbar::BusbarIndex;
+use crate::factors::lambda_max;
+use crate::math::SQRT_3;
+use crate::part4::iec60909_4_6;
+use crate::{assert_cmplx_eq, cmplx};
+use crate::{
+    ACSystem, AsynchronousMotor, Branch, Busbar, Cable, ConverterDrive, DoublyFedWindUnit,
+    FullConverterUnit, FullConverterWindUnit, NetworkFeeder, NetworkTransformer, OverheadLine,
+    PowerStationUnit, SynchronousGenerator, SynchronousMotor, TapChanger,
+};
+
+fn synchronous_motor(overexcited: bool) -> Result<SynchronousMotor<&'static str>> {
+    Ok(SynchronousMotor::new()
+        .node("M")
+        .ur(10)
+        .sr(5_000)
+        .cos_phi(0.8)
+        .overexcited(overexcited)
+        .r(0.02)
+        .xdpp(0.15)
+        .xdsat(1.6)
+        .build()?)
+}
+
+fn network(node: &'static str) -> Result<(Busbar<&'static str>, NetworkFeeder<&'static str>)> {
+    let busbar = Busbar::new().nodes(["Q", node]).un(110).cmax(1.1).build()?;
+    let feeder = NetworkFeeder::new()
+        .node("Q")
+        .ur(110)
+        .ikss(10)
+        .rx(0.1)
+        .build()?;
+    Ok((busbar, feeder))
+}
+
+#[test]
+fn test_synchronous_motor() -> Result<()> {
+    let busbar = Busbar::new().node("M").un(10).cmax(1.1).build()?;
+    let busbars = [busbar];
+    let busbar_index = BusbarIndex::new(&busbars);
+
+    let xdpp = 0.15 * 10e3 * 10e3 / 5e6;
+
+    let m = synchronous_motor(true)?;
+    let km = 1.1 / (1.0 + 0.15 * 0.6);
+    assert_cmplx_eq!(
+        m.impedance(false, &busbar_index)?,
+        cmplx!(km * 0.02, km * xdpp),
+        epsilon = 1e-9
+    );
+
+    let m = synchronous_motor(false)?;
+    let km = 1.1 / (1.0 - 0.15 * 0.6);
+    assert_cmplx_eq!(
+        m.impedance(false, &busbar_index)?,
+        cmplx!(km * 0.02, km * xdpp),
+        epsilon = 1e-9
+    );
+
+    // Peak impedance with the fictitious resistance of a machine below 100 MVA.
+    assert_cmplx_eq!(
+        m.impedance(true, &busbar_index)?,
+        cmplx!(km * 0.07 * xdpp, km * xdpp),
+        epsilon = 1e-9
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_synchronous_motor_short_circuit() -> Result<()> {
+    let tmin = 0.1;
+
+    // Synchronous compensator feeding the short circuit alone.
+    let busbar = Busbar::new().node("M").un(10).cmax(1.1).build()?;
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbar(busbar)
+        .synchronous_motor(synchronous_motor(true)?)
+        .build()?;
+
+    let solver = spsolve::rlu::RLU::default();
+    let faults = net.short_circuit(solver, tmin)?;
+    assert_eq!(faults.len(), 1);
+    let fault = &faults[0];
+
+    // ZM = KM (R + jX"d) = 1.0092 (0.02 + j3.0) Ohm, IrM = 0.2887 kA.
+    // I"kM = 2.0977 kA, I"kM/IrM = 7.267, mu = 0.6904, lambda_max = 1.902.
+    assert_abs_diff_eq!(fault.ikss, 2.0977, epsilon = 1e-4);
+    assert_abs_diff_eq!(fault.ib, 1.4482, epsilon = 1e-4);
+    assert_abs_diff_eq!(fault.ik, 0.5491, epsilon = 1e-4);
+
+    Ok(())
+}
+
+#[test]
+fn test_lambda_max() {
+    // X"d = 0.15, Xdsat = 1.8, sin(phi) = 0.6: Ef = sqrt(0.8^2 + 2.4^2) = 2.5298 p.u.
+
+    // Terminal fault, I"kG/IrG = 8: no external reactance, lambda_max = Ufmax Ef / Xdsat.
+    assert_abs_diff_eq!(
+        lambda_max(8.0, 0.15, 1.8, 0.6, 1.3),
+        1.827094,
+        epsilon = 1e-6
+    );
+    assert_abs_diff_eq!(
+        lambda_max(8.0, 0.15, 1.8, 0.6, 1.6),
+        2.248731,
+        epsilon = 1e-6
+    );
+
+    // I"kG/IrG = 3: external reactance 1/3 - 0.15 = 0.1833 p.u.
+    assert_abs_diff_eq!(
+        lambda_max(3.0, 0.15, 1.8, 0.6, 1.3),
+        1.658203,
+        epsilon = 1e-6
+    );
+
+    // I"kG/IrG = 1.2: the model gives 1.3243, limited to the initial current.
+    assert_abs_diff_eq!(lambda_max(1.2, 0.15, 1.8, 0.6, 1.3), 1.2, epsilon = 1e-6);
+}
+
+#[test]
+fn test_meshed_steady_state_current() -> Result<()> {
+    let tmin = 0.1;
+
+    let busbar = Busbar::new()
+        .nodes(["Q", "M", "A"])
+        .un(10)
+        .cmax(1.1)
+        .build()?;
+    let q = NetworkFeeder::new()
+        .node("Q")
+        .ur(10)
+        .ikss(20)
+        .rx(0.1)
+        .build()?;
+    let a = AsynchronousMotor::new()
+        .node("A")
+        .ur(10)
+        .pr(2_000)
+        .cos_phi(0.85)
+        .eta(95)
+        .ilr_ir(5)
+        .p(2)
+        .build()?;
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbar(busbar)
+        .feeder(q)
+        .synchronous_motor(synchronous_motor(true)?)
+        .motor(a)
+        .build()?;
+
+    let solver = spsolve::rlu::RLU::default();
+    let fault = &net.short_circuit(solver, tmin)?[0];
+
+    // Two sources: Ik = I"k without the asynchronous motor.
+    // ZQ = 0.0316 + j0.3160 Ohm, ZM = 0.0202 + j3.0275 Ohm.
+    assert_abs_diff_eq!(fault.ik, 22.089, epsilon = 1e-3);
+    assert!(fault.ikss > fault.ik);
+
+    Ok(())
+}
+
+#[test]
+fn test_wind_power_station_units() -> Result<()> {
+    let (busbar, q) = network("W")?;
+    let wd = DoublyFedWindUnit::new()
+        .node("W")
+        .ur(110)
+        .iwdmax(1.0)
+        .ikwdmax(0.3)
+        .build()?;
+    let wf = FullConverterWindUnit::new()
+        .node("W")
+        .iskwf(0.2)
+        .ikwfmax(0.2)
+        .build()?;
+
+    // ZWD = sqrt(2) 1.7 110 kV / (sqrt(3) 1.0 kA) = 152.684861 Ohm.
+    assert_abs_diff_eq!(wd.impedance()?.norm(), 152.684861, epsilon = 1e-6);
+    assert_abs_diff_eq!(
+        wd.impedance()?.re / wd.impedance()?.im,
+        0.1,
+        epsilon = 1e-12
+    );
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbar(busbar)
+        .feeder(q)
+        .doubly_fed_wind_unit(wd)
+        .full_converter_wind_unit(wf)
+        .build()?;
+
+    let solver = spsolve::rlu::RLU::default();
+    let fault = &net.short_circuit(solver, 0.1)?[0];
+
+    // ZQ = 0.695127 + j6.951268 Ohm and ZWD = 15.192711 + j151.927115 Ohm, both with
+    // R/X = 0.1: I"k = 10 + 0.457540 kA, plus 0.2 kA of the full converter unit.
+    assert_abs_diff_eq!(fault.ikss, 10.657540, epsilon = 1e-6);
+    assert_abs_diff_eq!(fault.ib, 10.657540, epsilon = 1e-6);
+    // Fed from the network and the wind power station unit: the network is treated as meshed.
+    assert_abs_diff_eq!(fault.ik, 10.657540, epsilon = 1e-6);
+
+    // R/X = 0.1 in all branches, at the system and the equivalent frequency: kappa = 1.746002
+    // and 1.15 kappa > 2. The full converter unit adds sqrt(2) 0.2 kA.
+    assert_abs_diff_eq!(fault.ip50, 29.861232, epsilon = 1e-6);
+    assert_abs_diff_eq!(fault.ip20, 26.104804, epsilon = 1e-6);
+
+    Ok(())
+}
+
+#[test]
+fn test_full_converter_unit() -> Result<()> {
+    let a = Busbar::new()
+        .nodes(["QA", "LA"])
+        .un(110)
+        .cmax(1.1)
+        .build()?;
+    let b = Busbar::new()
+        .nodes(["QB", "LB", "PV"])
+        .un(110)
+        .cmax(1.1)
+        .build()?;
+    let qa = NetworkFeeder::new()
+        .node("QA")
+        .ur(110)
+        .ikss(10)
+        .rx(0.1)
+        .build()?;
+    let qb = NetworkFeeder::new()
+        .node("QB")
+        .ur(110)
+        .ikss(5)
+        .rx(0.1)
+        .build()?;
+    let l = OverheadLine::new()
+        .node_i("LA")
+        .node_j("LB")
+        .l(20)
+        .rl(0.12)
+        .xl(0.39)
+        .build()?;
+    let pv = FullConverterUnit::new()
+        .node("PV")
+        .iskpf(0.3)
+        .ikpfmax(0.25)
+        .build()?;
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbars([a, b])
+        .feeders([qa, qb])
+        .line(l)
+        .full_converter_unit(pv)
+        .build()?;
+
+    let solver = spsolve::rlu::RLU::default();
+    let faults = net.short_circuit(solver, 0.1)?;
+
+    // ZQA = 0.695127 + j6.951268 Ohm, ZQB = 1.390254 + j13.902537 Ohm, ZL = 2.4 + j7.8 Ohm.
+    // Z_AA = 0.621010 + j5.270187 Ohm, Z_AB = 0.148234 + j3.362162 Ohm,
+    // Z_BB = 1.093785 + j7.178212 Ohm.
+
+    // Short circuit at A, remote from the converter: I"k = 13.164500 kA from the network and
+    // |Z_AB| / |Z_AA| = 0.634191 of the converter current.
+    let fault = &faults[0];
+    assert_abs_diff_eq!(fault.ikss, 13.354757, epsilon = 1e-6);
+    assert_abs_diff_eq!(fault.ib, 13.354757, epsilon = 1e-6);
+    assert_abs_diff_eq!(fault.ik, 13.323048, epsilon = 1e-6);
+
+    // Short circuit at B, at the converter terminals: I"k = 9.621091 kA from the network.
+    let fault = &faults[1];
+    assert_abs_diff_eq!(fault.ikss, 9.921091, epsilon = 1e-6);
+    assert_abs_diff_eq!(fault.ik, 9.871091, epsilon = 1e-6);
+
+    Ok(())
+}
+
+#[test]
+fn test_full_converter_unit_off_busbar() -> Result<()> {
+    let (b, q) = network("C")?;
+    let c = Cable::new()
+        .node_i("C")
+        .node_j("PV")
+        .l(5)
+        .rl(0.1)
+        .xl(0.1)
+        .build()?;
+    let pv = FullConverterUnit::new()
+        .node("PV")
+        .iskpf(0.3)
+        .ikpfmax(0.25)
+        .build()?;
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbar(b)
+        .feeder(q)
+        .cable(c)
+        .full_converter_unit(pv)
+        .build()?;
+
+    let solver = spsolve::rlu::RLU::default();
+    let fault = &net.short_circuit(solver, 0.1)?[0];
+
+    // The cable is radial, so the source current reaches the busbar unchanged.
+    assert_abs_diff_eq!(fault.ikss, 10.0 + 0.3, epsilon = 1e-9);
+    assert_abs_diff_eq!(fault.ik, 10.0 + 0.25, epsilon = 1e-9);
+
+    // A unit without any connection to the network.
+    let mut net = net;
+    net.full_converter_units[0].node = "X";
+    let err = net.short_circuit(spsolve::rlu::RLU::default(), 0.1);
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "current source 1 error: not connected"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_converter_drive() -> Result<()> {
+    let tmin = 0.1;
+
+    let busbar = Busbar::new().nodes(["Q", "D"]).un(10).cmax(1.1).build()?;
+    let q = NetworkFeeder::new()
+        .node("Q")
+        .ur(10)
+        .ikss(20)
+        .rx(0.1)
+        .build()?;
+    let d = ConverterDrive::new().node("D").ur(10).ir(0.5).build()?;
+
+    // ZM = (1/3) 10 kV / (sqrt(3) 0.5 kA) = 3.849 Ohm, XM = ZM / sqrt(1.01), RM = 0.1 XM.
+    assert_cmplx_eq!(d.impedance()?, cmplx!(0.38299, 3.82990), epsilon = 1e-5);
+
+    // With R/X = 0.5: XM = ZM / sqrt(1.25).
+    let mut d5 = d.clone();
+    d5.rx = 0.5;
+    assert_cmplx_eq!(d5.impedance()?, cmplx!(1.72133, 3.44265), epsilon = 1e-5);
+    assert_abs_diff_eq!(d5.impedance()?.norm(), 3.84900, epsilon = 1e-5);
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbar(busbar)
+        .feeder(q)
+        .converter_drive(d)
+        .build()?;
+
+    let solver = spsolve::rlu::RLU::default();
+    let fault = &net.short_circuit(solver, tmin)?[0];
+
+    // Both with R/X = 0.1: I"kQ = 20 kA and I"kM = 1.1 10 kV / (sqrt(3) 3.849 Ohm) = 1.65 kA.
+    assert_abs_diff_eq!(fault.ikss, 21.65, epsilon = 1e-6);
+
+    // I"kM/IrM = 3.3, mu = 0.62 + 0.72 exp(-0.32 3.3) = 0.870448, IbM = 1.436239 kA.
+    assert_abs_diff_eq!(fault.ib, 21.436239, epsilon = 1e-6);
+    assert_abs_diff_eq!(fault.ik, 20.0, epsilon = 1e-6);
+
+    Ok(())
+}
+
+#[test]
+fn test_power_station_terminal_fault() -> Result<()> {
+    let tmin = 0.1;
+
+    let (q, feeder) = network("HV")?;
+    let g = Busbar::new().node("G").un(21).cmax(1.1).build()?;
+    let station = PowerStationUnit::new()
+        .generator(
+            SynchronousGenerator::new()
+                .node("G")
+                .ur(21)
+                .sr(150_000)
+                .cos_phi(0.85)
+                .r(0.005)
+                .xdpp(0.16)
+                .xdsat(1.8)
+                .p(5)
+                .build()?,
+        )
+        .transformer(
+            NetworkTransformer::new()
+                .node_hv("HV")
+                .node_lv("G")
+                .ur_hv(115)
+                .ur_lv(21)
+                .tap(TapChanger::new().step(1.0).min(-10).max(10).build()?)
+                .sr(150_000)
+                .ukr(13)
+                .pkr(400)
+                .build()?,
+        )
+        .build()?;
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, g])
+        .feeder(feeder)
+        .power_station(station)
+        .build()?;
+    let busbar_index = BusbarIndex::new(&net.busbars);
+    let s = &net.power_stations[0];
+
+    // ZG = 0.005 + j0.4704 Ohm, ZTLV = 0.00784 + j0.382120 Ohm, xT = 0.129973, sin(phi) = 0.526783.
+    // KG,S = 1.1 / (1 + 0.16 sin(phi)) = 1.014493, KT,S = 1.1 / (1 - xT sin(phi)) = 1.180850.
+    let (zg_s, zt_s) = s.terminal_impedances(false, &busbar_index)?;
+    assert_cmplx_eq!(zg_s, cmplx!(0.005072, 0.477218), epsilon = 1e-6);
+    assert_cmplx_eq!(zt_s, cmplx!(0.009258, 0.451226), epsilon = 1e-6);
+
+    // Without on-load tap-changer, KG,SO and KT,SO.
+    let mut so = s.clone();
+    so.transformer.tap = None;
+    let (zg_so, zt_so) = so.terminal_impedances(false, &busbar_index)?;
+    assert_cmplx_eq!(zg_so, cmplx!(0.004831, 0.454493), epsilon = 1e-6);
+    assert_cmplx_eq!(zt_so, cmplx!(0.008817, 0.429739), epsilon = 1e-6);
+
+    let faults = net.short_circuit(spsolve::rlu::RLU::default(), tmin)?;
+    assert_eq!(faults.len(), 2);
+    let fault = &faults[1];
+
+    // ZQ = (0.695127 + j6.951268) / (115/21)^2 = 0.023180 + j0.231797 Ohm.
+    // I"kG = 1.1 21 kV / (sqrt(3) |ZG,S|) = 27.945402 kA, I"kT = 19.504160 kA.
+    assert_abs_diff_eq!(fault.ikss, 47.449562, epsilon = 1e-6);
+    // I"kG/IrG = 6.776400, mu = 0.702336.
+    assert_abs_diff_eq!(fault.ib, 39.131210, epsilon = 1e-6);
+    // lambda_max = 1.3 Ef / Xdsat = 1.789074, IrG = 4.123930 kA.
+    assert_abs_diff_eq!(fault.ik, 26.882177, epsilon = 1e-6);
+
+    // Partial impedances in parallel, with RGf = 0.05 X"d: Zk = 0.013757 + j0.280934 Ohm and
+    // 1.15 kappa > 2. At 20 Hz: Zk = 0.013757 + j0.112374 Ohm, kappa = 1.866111.
+    assert_abs_diff_eq!(fault.ip50, 134.207629, epsilon = 1e-6);
+    assert_abs_diff_eq!(fault.ip20, 125.223195, epsilon = 1e-6);
+
+    Ok(())
+}
+
+#[test]
+fn test_power_station_auxiliary_fault() -> Result<()> {
+    let tmin = 0.1;
+
+    let (q, feeder) = network("HV")?;
+    let g = Busbar::new().node("G").un(21).cmax(1.1).build()?;
+    let a = Busbar::new().nodes(["A", "M"]).un(6).cmax(1.1).build()?;
+    let motor = AsynchronousMotor::new()
+        .node("M")
+        .ur(6)
+        .pr(5_000)
+        .cos_phi(0.86)
+        .eta(97)
+        .ilr_ir(5)
+        .p(2)
+        .build()?;
+    let station = PowerStationUnit::new()
+        .generator(
+            SynchronousGenerator::new()
+                .node("G")
+                .ur(21)
+                .sr(150_000)
+                .cos_phi(0.85)
+                .r(0.005)
+                .xdpp(0.16)
+                .xdsat(1.8)
+                .p(5)
+                .build()?,
+        )
+        .transformer(
+            NetworkTransformer::new()
+                .node_hv("HV")
+                .node_lv("G")
+                .ur_hv(115)
+                .ur_lv(21)
+                .tap(TapChanger::new().step(1.0).min(-10).max(10).build()?)
+                .sr(150_000)
+                .ukr(13)
+                .pkr(400)
+                .build()?,
+        )
+        .auxiliary_transformer(
+            NetworkTransformer::new()
+                .node_hv("G")
+                .node_lv("A")
+                .ur_hv(21)
+                .ur_lv(6.3)
+                .sr(15_000)
+                .ukr(10)
+                .pkr(100)
+                .build()?,
+        )
+        .auxiliary_motor(motor)
+        .build()?;
+
+    let net = ACSystem::new()
+        .frequency(50)
+        .busbars([q, g, a])
+        .feeder(feeder)
+        .power_station(station)
+        .build()?;
+    let busbar_index = BusbarIndex::new(&net.busbars);
+    let s = &net.power_stations[0];
+
+    let faults = net.short_circuit(spsolve::rlu::RLU::default(), tmin)?;
+    assert_eq!(faults.len(), 3);
+
+    let tr = 115.0 / 21.0;
+    let n = 21.0 / 6.3;
+    let zq = net.feeders[0].impedance(false, &busbar_index)? / (tr * tr);
+    let (zg_s, zt_s) = s.terminal_impedances(false, &busbar_index)?;
+    let zat = s.auxiliary_transformers[0].impedance(false, &busbar_index)?;
+    let zm = s.auxiliary_motors[0].impedance()?;
+
+    // Fault at the generator terminals, including the auxiliary motor fed back through the
+    // auxiliary transformer.
+    let e = 1.1 * 21.0 / SQRT_3;
+    let ikss_g = e / zg_s.norm();
+    let ikss_t = e / (zt_s + zq).norm();
+    let ikss_m = e / ((zat + zm) * n * n).norm();
+    assert_abs_diff_eq!(faults[1].ikss, ikss_g + ikss_t + ikss_m, epsilon = 1e-9);
+
+    // Fault at the auxiliary busbar, fed from the generator terminals and the motor.
+    let za = cmplx!(1) / (cmplx!(1) / zg_s + cmplx!(1) / (zt_s + zq));
+    let zk = cmplx!(1) / (cmplx!(1) / (za / (n * n) + zat) + cmplx!(1) / zm);
+    let fault = &faults[2];
+    assert_eq!(fault.node, "A");
+    assert_abs_diff_eq!(fault.ikss, 1.1 * 6.0 / SQRT_3 / zk.norm(), epsilon = 1e-9);
+    assert!(fault.ik < fault.ib && fault.ib < fault.ikss);
+    assert!(fault.ip20 > 0.0 && fault.ip50 > 2.0f64.sqrt() * fault.ikss);
+
+    // The auxiliary network is not part of the admittance matrix of the system.
+    let err = net
+        .double_earth_fault(spsolve::rlu::RLU::default(), &"A", &"Q")
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "double earth fault error: node on an auxiliary busbar of a power station unit"
+    );
+    let err = net
+        .branch_share(spsolve::rlu::RLU::default(), &"A", Branch::Line(0))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "node on an auxiliary busbar of a power station unit"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_power_station_low_voltage_impedance() -> Result<()> {
+    let net = iec60909_4_6()?;
+    let busbar_index = BusbarIndex::new(&net.busbars);
+
+    // The generator is stamped at the low-voltage node of the unit transformer, so the
+    // impedance on the low-voltage side must be the high-voltage one referred through tr².
+    for s in &net.power_stations {
+        let tr = s.transformer.ur_hv / s.transformer.ur_lv;
+        let z_hv = s.impedance(true, false, &busbar_index)?;
+        let z_lv = s.impedance(false, false, &busbar_index)?;
+        assert_cmplx_eq!(z_lv * cmplx!(tr * tr), z_hv, epsilon = 1e-9);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_power_station_not_on_busbar() -> Result<()> {
+    let mut net = iec60909_4_6()?;
+    let hv = net.power_stations[0].transformer.node_hv;
+    net.busbars.retain(|b| !b.nodes.contains(&hv));
+    let busbar_index = BusbarIndex::new(&net.busbars);
+
+    let s = &net.power_stations[0];
+    assert!(s.impedance(true, false, &busbar_index).is_err());
+    assert!(net
+        .short_circuit(spsolve::rlu::RLU::default(), 0.1)
+        .is_err());
+
+    Ok(())
+}
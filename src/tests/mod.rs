@@ -4,7 +4,11 @@ mod iec60909_4_4;
 mod iec60909_4_5;
 mod iec60909_4_6;
 
+mod breaking_test;
+mod earth_fault_test;
 mod impedance_test;
+mod machine_test;
+mod superposition_test;
 
 #[macro_export]
 macro_rules! assert_cmplx_eq {
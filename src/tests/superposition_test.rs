@@ -0,0 +1,156 @@
+use anyhow::Result;
+use approx::assert_abs_diff_eq;
+use num_complex::Complex64;
+
+use spsolve::rlu::RLU;
+
+use crate::busbar::BusbarIndex;
+use crate::cmplx;
+use crate::math::SQRT_3;
+use crate::{
+    ACSystem, Busbar, Load, NetworkFeeder, NodeVoltage, OperatingPoint, OverheadLine,
+    PowerInjection, SynchronousGenerator,
+};
+
+fn network() -> Result<ACSystem<&'static str>> {
+    let a = Busbar::new().nodes(["Q", "LA"]).un(110).cmax(1.1).build()?;
+    let b = Busbar::new().nodes(["LB"]).un(110).cmax(1.1).build()?;
+    let q = NetworkFeeder::new()
+        .node("Q")
+        .ur(110)
+        .ikss(10)
+        .rx(0.1)
+        .build()?;
+    let l = OverheadLine::new()
+        .node_i("LA")
+        .node_j("LB")
+        .l(40)
+        .rl(0.12)
+        .xl(0.39)
+        .build()?;
+
+    Ok(ACSystem::new()
+        .frequency(50)
+        .busbars([a, b])
+        .feeder(q)
+        .line(l)
+        .build()?)
+}
+
+#[test]
+fn test_superposition_no_load() -> Result<()> {
+    let net = network()?;
+    let busbar_index = BusbarIndex::new(&net.busbars);
+    let op = OperatingPoint::new().build()?;
+
+    let v = net.load_flow(RLU::default(), &op)?;
+    for u in v.values() {
+        assert_abs_diff_eq!(u.norm(), 110.0, epsilon = 1e-9);
+    }
+
+    let faults = net.superposition(RLU::default(), &op)?;
+
+    // Without the voltage factor c of the equivalent voltage source.
+    let zq = net.feeders[0].impedance(false, &busbar_index)?;
+    assert_abs_diff_eq!(faults[0].ikss, 10.0 / 1.1, epsilon = 1e-9);
+
+    let zl = net.lines[0].impedance(net.frequency)?;
+    let ikss_b = 110.0 / SQRT_3 / (zq + zl).norm();
+    assert_abs_diff_eq!(faults[1].ikss, ikss_b, epsilon = 1e-9);
+
+    let ikss = net.short_circuit(RLU::default(), 0.1)?;
+    assert_abs_diff_eq!(faults[1].ikss * 1.1, ikss[1].ikss, epsilon = 1e-9);
+    assert_abs_diff_eq!(faults[1].ip * 1.1, ikss[1].ip50, epsilon = 1e-9);
+    assert_abs_diff_eq!(faults[1].ub, 110.0, epsilon = 1e-9);
+
+    Ok(())
+}
+
+#[test]
+fn test_superposition_load() -> Result<()> {
+    let net = network()?;
+    let busbar_index = BusbarIndex::new(&net.busbars);
+    let op = OperatingPoint::new()
+        .injection(PowerInjection::new().node("LB").p(-60).q(-20).build()?)
+        .uq(1.05)
+        .build()?;
+
+    let v = net.load_flow(RLU::default(), &op)?;
+
+    // Power delivered through the feeder and the line to the load.
+    let eq = cmplx!(1.05 * 110.0 / SQRT_3);
+    let z = net.feeders[0].impedance(false, &busbar_index)? + net.lines[0].impedance(50.0)?;
+    let ub = v["LB"] / SQRT_3;
+    let s = ub * ((eq - ub) / z).conj() * 3.0;
+    assert_abs_diff_eq!(s.re, 60.0, epsilon = 1e-6);
+    assert_abs_diff_eq!(s.im, 20.0, epsilon = 1e-6);
+    assert!(v["LB"].norm() < v["LA"].norm());
+
+    let faults = net.superposition(RLU::default(), &op)?;
+    assert_abs_diff_eq!(faults[1].ikss, ub.norm() / z.norm(), epsilon = 1e-6);
+    assert_abs_diff_eq!(faults[1].ub, v["LB"].norm(), epsilon = 1e-9);
+
+    Ok(())
+}
+
+#[test]
+fn test_superposition_generator() -> Result<()> {
+    let mut net = network()?;
+    net.generators.push(
+        SynchronousGenerator::new()
+            .node("LB")
+            .ur(110)
+            .sr(100_000)
+            .cos_phi(0.8)
+            .r(2.0)
+            .xdpp(0.15)
+            .build()?,
+    );
+    let busbar_index = BusbarIndex::new(&net.busbars);
+
+    // Known pre-fault voltage at the generator busbar.
+    let op = OperatingPoint::new()
+        .voltage(NodeVoltage::new().node("LB").u(113).angle(5).build()?)
+        .build()?;
+
+    let faults = net.superposition(RLU::default(), &op)?;
+
+    let zq = net.feeders[0].impedance(false, &busbar_index)? + net.lines[0].impedance(50.0)?;
+    let zg = cmplx!(2.0, 0.15 * 110.0 * 110.0 / 100.0);
+    let zk = Complex64::new(1.0, 0.0) / (cmplx!(1) / zq + cmplx!(1) / zg);
+    assert_abs_diff_eq!(faults[1].ikss, 113.0 / SQRT_3 / zk.norm(), epsilon = 1e-9);
+
+    // Nominal voltage at busbars without a given voltage.
+    let za = net.feeders[0].impedance(false, &busbar_index)?;
+    let zk = cmplx!(1) / (cmplx!(1) / za + cmplx!(1) / (net.lines[0].impedance(50.0)? + zg));
+    assert_abs_diff_eq!(faults[0].ikss, 110.0 / SQRT_3 / zk.norm(), epsilon = 1e-9);
+
+    Ok(())
+}
+
+#[test]
+fn test_load_voltage_dependence() -> Result<()> {
+    let mut net = network()?;
+    net.loads
+        .push(Load::new().node("LB").p(60).q(20).np(2).nq(2).build()?);
+    let busbar_index = BusbarIndex::new(&net.busbars);
+
+    let op = OperatingPoint::new().build()?;
+    let v = net.load_flow(RLU::default(), &op)?;
+
+    // Constant impedance load.
+    let zload = cmplx!(110.0 * 110.0) / cmplx!(60, 20).conj();
+    let z = net.feeders[0].impedance(false, &busbar_index)? + net.lines[0].impedance(50.0)?;
+    let ub = 110.0 * (zload / (zload + z)).norm();
+    assert_abs_diff_eq!(v["LB"].norm(), ub, epsilon = 1e-6);
+
+    let load = &net.loads[0];
+    assert_abs_diff_eq!(load.power(ub, 110.0).re, 60.0 * (ub / 110.0).powi(2));
+
+    // Loads are neglected by the equivalent voltage source method.
+    let faults = net.short_circuit(RLU::default(), 0.1)?;
+    let faults_no_load = network()?.short_circuit(RLU::default(), 0.1)?;
+    assert_eq!(faults, faults_no_load);
+
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use std::f64::consts::PI;
+
+use anyhow::{format_err, Result};
+use derive_builder::Builder;
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+use crate::cmplx;
+use crate::math::{invert, ONE};
+use crate::traits::Sq;
+
+pub(crate) const MU_0: f64 = 4.0 * PI * 1e-7; // H/m
+
+/// Conductor, or bundle of conductors, on an overhead line tower.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into))]
+pub struct Conductor {
+    /// Horizontal position (m).
+    pub x: f64,
+
+    /// Height above ground (m).
+    pub y: f64,
+
+    /// Resistance of a single conductor (Ohms/km).
+    pub r: f64,
+
+    /// Radius of a single conductor (m).
+    pub radius: f64,
+
+    /// Number of bundled conductors.
+    #[builder(setter(into = false), default = "1")]
+    pub n: usize,
+
+    /// Radius of the circle through the centres of the bundled conductors (m).
+    pub bundle_radius: f64,
+
+    /// Relative permeability of the conductor.
+    #[builder(default = "1.0")]
+    pub mu_r: f64,
+}
+
+impl Conductor {
+    pub fn new() -> ConductorBuilder {
+        ConductorBuilder::default()
+    }
+
+    /// Equivalent radius of the bundle (m).
+    pub fn equivalent_radius(&self) -> f64 {
+        let n = self.n.max(1) as f64;
+        (n * self.radius * self.bundle_radius.powf(n - 1.0)).powf(1.0 / n)
+    }
+
+    fn distance(&self, other: &Conductor) -> f64 {
+        ((self.x - other.x).sq() + (self.y - other.y).sq()).sqrt()
+    }
+}
+
+/// Arrangement of the phase conductors and earth wires of a single circuit overhead line.
+/// The line is assumed to be transposed.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into))]
+pub struct TowerGeometry {
+    /// Phase conductors L1, L2 and L3.
+    #[builder(setter(each(name = "phase")))]
+    pub phases: Vec<Conductor>,
+
+    /// Earth wires, earthed at each tower.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter(each(name = "earth_wire")))]
+    pub earth_wires: Vec<Conductor>,
+
+    /// Earth resistivity (Ohm m).
+    #[builder(default = "100.0")]
+    pub rho: f64,
+}
+
+impl TowerGeometry {
+    pub fn new() -> TowerGeometryBuilder {
+        TowerGeometryBuilder::default()
+    }
+
+    /// Equivalent earth-return depth (m).
+    pub fn earth_return_depth(&self, f: f64) -> f64 {
+        earth_return_depth(f, self.rho)
+    }
+
+    /// Impedance matrix (Ohms/km) of the phase conductors with earth return using Carson's
+    /// equations. The earth wires are eliminated by Kron reduction.
+    pub fn phase_impedance_matrix(&self, f: f64) -> Result<[[Complex64; 3]; 3]> {
+        if self.phases.len() != 3 {
+            return Err(format_err!(
+                "tower geometry error: {} phase conductors",
+                self.phases.len()
+            ));
+        }
+        let z = self.carson(f);
+
+        let mut zp = [[Complex64::default(); 3]; 3];
+        for (i, row) in zp.iter_mut().enumerate() {
+            for (j, zij) in row.iter_mut().enumerate() {
+                *zij = z(i, j);
+            }
+        }
+        if self.earth_wires.is_empty() {
+            return Ok(zp);
+        }
+
+        // Kron reduction: Zp - Zpe Zee^-1 Zep
+        let ne = self.earth_wires.len();
+        let zee: Vec<Vec<Complex64>> = (0..ne)
+            .map(|i| (0..ne).map(|j| z(3 + i, 3 + j)).collect())
+            .collect();
+        let yee = invert_earth_wires(&zee)?;
+        for j in 0..3 {
+            let x: Vec<Complex64> = yee
+                .iter()
+                .map(|row| (0..ne).map(|k| row[k] * z(3 + k, j)).sum())
+                .collect();
+            for (i, row) in zp.iter_mut().enumerate() {
+                let zie: Complex64 = (0..ne).map(|k| z(i, 3 + k) * x[k]).sum();
+                row[j] -= zie;
+            }
+        }
+        Ok(zp)
+    }
+
+    /// Reduction factor of the earth wires, the part of the zero-sequence current 3I0 of the
+    /// line returning through earth. Without earth wires the factor is 1.
+    pub fn reduction_factor(&self, f: f64) -> Result<Complex64> {
+        if self.earth_wires.is_empty() {
+            return Ok(cmplx!(1));
+        }
+        let z = self.carson(f);
+
+        // Current induced in each earth wire by 3I0 = 1, shared equally by the phases.
+        let ne = self.earth_wires.len();
+        let zee: Vec<Vec<Complex64>> = (0..ne)
+            .map(|i| (0..ne).map(|j| z(3 + i, 3 + j)).collect())
+            .collect();
+        let zel: Vec<Complex64> = (0..ne)
+            .map(|i| (0..3).map(|j| z(3 + i, j)).sum::<Complex64>() / 3.0)
+            .collect();
+        let iq: Vec<Complex64> = invert_earth_wires(&zee)?
+            .iter()
+            .map(|row| row.iter().zip(&zel).map(|(y, z)| y * z).sum())
+            .collect();
+
+        Ok(ONE - iq.iter().sum::<Complex64>())
+    }
+
+    /// Self and mutual impedances (Ohms/km) of the phase conductors, followed by the earth
+    /// wires, with earth return (Carson).
+    fn carson(&self, f: f64) -> impl Fn(usize, usize) -> Complex64 + '_ {
+        let omega = 2.0 * PI * f;
+        let delta = self.earth_return_depth(f);
+
+        let re = omega * MU_0 / 8.0 * 1e3; // Ohms/km
+        let xk = omega * MU_0 / (2.0 * PI) * 1e3; // Ohms/km
+
+        let conductors: Vec<&Conductor> = self.phases.iter().chain(&self.earth_wires).collect();
+        move |i: usize, j: usize| {
+            let (a, b) = (conductors[i], conductors[j]);
+            if i == j {
+                let n = a.n.max(1) as f64;
+                let x = a.mu_r / (4.0 * n) + (delta / a.equivalent_radius()).ln();
+                cmplx!(a.r / n + re, xk * x)
+            } else {
+                cmplx!(re, xk * (delta / a.distance(b)).ln())
+            }
+        }
+    }
+
+    /// Positive and zero-sequence impedances (Ohms/km) of the transposed line.
+    pub fn sequence_impedances(&self, f: f64) -> Result<(Complex64, Complex64)> {
+        let zp = self.phase_impedance_matrix(f)?;
+
+        let zs = (zp[0][0] + zp[1][1] + zp[2][2]) / 3.0;
+        let zm = (zp[0][1] + zp[1][2] + zp[2][0]) / 3.0;
+
+        Ok((zs - zm, zs + 2.0 * zm))
+    }
+}
+
+/// Equivalent earth-return depth (m) for the earth resistivity `rho` (Ohm m).
+pub(crate) fn earth_return_depth(f: f64, rho: f64) -> f64 {
+    let omega = 2.0 * PI * f;
+    1.851 / (omega * MU_0 / rho).sqrt()
+}
+
+/// Inverse of the self and mutual impedances of the earth wires.
+fn invert_earth_wires(zee: &[Vec<Complex64>]) -> Result<Vec<Vec<Complex64>>> {
+    invert(zee).map_err(|_| format_err!("tower geometry error: singular earth wire matrix"))
+}
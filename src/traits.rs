@@ -7,3 +7,16 @@ impl Sq for f64 {
         self.powi(2)
     }
 }
+
+/// Element contributing a limited short-circuit current that is independent of the network
+/// impedance, such as a power station unit with full size converter.
+pub(crate) trait CurrentSource<N> {
+    /// Connection point of the source.
+    fn node(&self) -> &N;
+
+    /// Source current in case of a three-phase short circuit (kA).
+    fn isk(&self) -> f64;
+
+    /// Maximum steady-state short-circuit current (kA).
+    fn ikmax(&self) -> f64;
+}
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::busbar::{c_or_default, voltage_correction_factor, BusbarIndex};
 use crate::cmplx;
+use crate::math::SQRT_3;
+use crate::tap::{TapChanger, TapSelection};
 use crate::traits::Sq;
 
 /// Two-winding network transformer.
@@ -33,22 +35,46 @@ pub struct NetworkTransformer<N: Default> {
     /// Rated resistive component of the short-circuit voltage in per cent.
     pub urr: Option<f64>,
 
-    /// Impedance correction factor if the long-term operating conditions of network transformers
-    /// before the short circuit are known. Highest operating voltage before short circuit.
+    /// Highest operating voltage before the short circuit on the high-voltage side (kV). If the
+    /// long-term operating conditions before the short circuit are known the correction factor
+    /// is calculated with (12b).
     pub ub: Option<f64>,
 
-    /// Highest operating current before short circuit.
+    /// Highest operating current before the short circuit on the high-voltage side (kA).
     pub ib: f64,
 
-    /// Angle of power factor before short circuit.
+    /// Angle of the power factor before the short circuit (rad). Positive for inductive
+    /// operation.
     pub phib: f64,
 
-    /// Range of transformer voltage regulation (%).
-    pub p: f64,
+    /// Tap changer on the high-voltage winding.
+    pub tap: Option<TapChanger>,
 
+    /// Ratio of the zero-sequence reactance to the positive-sequence reactance.
     pub x0x: f64,
 
+    /// Ratio of the zero-sequence resistance to the positive-sequence resistance.
     pub r0r: f64,
+
+    /// Connection of the windings in the zero-sequence system.
+    pub zero_sequence: ZeroSequenceConnection,
+}
+
+/// Connection of the windings of a transformer in the zero-sequence system.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub enum ZeroSequenceConnection {
+    /// No path for zero-sequence currents, such as Yy or Dd.
+    #[default]
+    Open,
+
+    /// Earthed star on the high-voltage side and delta on the low-voltage side (YNd).
+    Hv,
+
+    /// Delta on the high-voltage side and earthed star on the low-voltage side (Dyn).
+    Lv,
+
+    /// Earthed star on both sides (YNyn).
+    Both,
 }
 
 impl<N: Clone + Default + Eq + core::hash::Hash> NetworkTransformer<N> {
@@ -57,12 +83,10 @@ impl<N: Clone + Default + Eq + core::hash::Hash> NetworkTransformer<N> {
     }
 
     pub fn impedance(&self, hv: bool, busbar_index: &BusbarIndex<N>) -> Result<Complex64> {
-        let ukr = self.ukr;
         let sr = self.sr * 1000.0;
-        let pkr = self.pkr * 1000.0;
 
         let (ur, un, c) = if hv {
-            let ur = self.ur_hv * 1000.0;
+            let ur = self.ur_hv_tap() * 1000.0;
 
             let (mut un, mut c) = if let Some(busbar) = busbar_index.busbar(&self.node_hv) {
                 let un = busbar.un * 1000.0;
@@ -99,6 +123,49 @@ impl<N: Clone + Default + Eq + core::hash::Hash> NetworkTransformer<N> {
             (ur, un, c)
         };
 
+        let zz = self.uncorrected_impedance(hv)?;
+        let x = zz.im;
+
+        let xr = x / (ur.sq() / sr); // relative reactance of the transformer
+        let k = if let Some(ub) = self.ub {
+            // Refer the operating voltage to the side of the nominal system voltage.
+            let ur_hv = self.ur_hv_tap() * 1000.0;
+            let ub = ub * 1000.0 * (ur / ur_hv);
+            let ib = self.ib * 1000.0;
+            let ir = sr / (SQRT_3 * ur_hv); // Rated current on the high-voltage side.
+            let phib = self.phib;
+
+            (un / ub) * (c / (1.0 + xr * (ib / ir) * phib.sin())) // (12b)
+        } else {
+            0.95 * (c / (1.0 + (0.6 * xr))) // (12a)
+        };
+
+        let zk = zz * cmplx!(k);
+
+        Ok(zk)
+    }
+
+    /// Zero-sequence short-circuit impedance with the correction factor KT, on the
+    /// high-voltage side if `hv` is true or the low-voltage side otherwise.
+    pub fn zero_sequence_impedance(
+        &self,
+        hv: bool,
+        busbar_index: &BusbarIndex<N>,
+    ) -> Result<Complex64> {
+        let z = self.impedance(hv, busbar_index)?;
+
+        Ok(cmplx!(z.re * self.r0r, z.im * self.x0x))
+    }
+
+    /// Short-circuit impedance of the transformer without correction factor, on the
+    /// high-voltage side if `hv` is true or the low-voltage side otherwise.
+    pub fn uncorrected_impedance(&self, hv: bool) -> Result<Complex64> {
+        let ukr = self.ukr;
+        let sr = self.sr * 1000.0;
+        let pkr = self.pkr * 1000.0;
+
+        let ur = if hv { self.ur_hv_tap() } else { self.ur_lv } * 1000.0;
+
         let z = (ukr / 100.0) * (ur.sq() / sr); // (7)
 
         // (8)
@@ -110,27 +177,27 @@ impl<N: Clone + Default + Eq + core::hash::Hash> NetworkTransformer<N> {
             return Err(format_err!("uRr or Pkr must be specified"));
         };
 
-        //let urr = (pkr / sr) * 100.0;
-        //let uxr = (ukr*ukr - urr*urr).sqrt();
-
         let x = (z * z - r * r).sqrt(); // (9)
 
-        let zz = cmplx!(r, x);
+        Ok(cmplx!(r, x))
+    }
 
-        let xr = x / (ur.sq() / sr); // relative reactance of the transformer
-        let k = if self.ub.is_some() {
-            let ub = self.ub.unwrap() * 1000.0;
-            let ib = self.ib * 1000.0;
-            let ir = sr / ur; // TODO: test
-            let phib = self.phib;
+    /// Rated voltage of the high-voltage winding (kV) at the position of an off-load tap
+    /// changer. The impedance of a transformer with on-load tap-changer is related to the
+    /// main position.
+    pub fn ur_hv_tap(&self) -> f64 {
+        match &self.tap {
+            Some(tap) => self.ur_hv * (1.0 + tap.permanent()),
+            None => self.ur_hv,
+        }
+    }
 
-            (un / ub) * (c / (1.0 + xr * (ib / ir) * phib.sin())) // (12b) TODO: test
-        } else {
-            0.95 * (c / (1.0 + (0.6 * xr))) // (12a)
+    /// Transformation ratio at the selected tap position.
+    pub fn ratio(&self, selection: TapSelection) -> f64 {
+        let ur_hv = match &self.tap {
+            Some(tap) => self.ur_hv * tap.factor(tap.select(selection)),
+            None => self.ur_hv,
         };
-
-        let zk = zz * cmplx!(k);
-
-        Ok(zk)
+        ur_hv / self.ur_lv
     }
 }
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::busbar::{voltage_correction_factor, BusbarIndex};
 use crate::cmplx;
+use crate::tap::{TapChanger, TapSelection};
 use crate::traits::Sq;
 
 #[derive(Clone, Copy)]
@@ -93,8 +94,8 @@ pub struct ThreeWindingTransformer<N: Default> {
     /// sides.
     pub uxr_mv_lv: f64,
 
-    /// Range of transformer voltage adjustment (%).
-    pub p: f64,
+    /// Tap changer on the high-voltage winding.
+    pub tap: Option<TapChanger>,
 }
 
 impl<N: Clone + Default + Eq + core::hash::Hash> ThreeWindingTransformer<N> {
@@ -107,15 +108,49 @@ impl<N: Clone + Default + Eq + core::hash::Hash> ThreeWindingTransformer<N> {
         side: TransformerSide,
         busbar_index: &BusbarIndex<N>,
     ) -> Result<(Complex64, Complex64, Complex64)> {
-        let zk_hv_mv = self.side_impedance(side, TransformerSides::HvMv, busbar_index)?;
+        let zk_hv_mv = self.side_impedance(side, TransformerSides::HvMv, Some(busbar_index))?;
 
-        let zk_hv_lv = self.side_impedance(side, TransformerSides::HvLv, busbar_index)?;
+        let zk_hv_lv = self.side_impedance(side, TransformerSides::HvLv, Some(busbar_index))?;
 
-        let zk_mv_lv = self.side_impedance(side, TransformerSides::MvLv, busbar_index)?;
+        let zk_mv_lv = self.side_impedance(side, TransformerSides::MvLv, Some(busbar_index))?;
 
-        let z_hv = 0.5 * (zk_hv_mv + zk_hv_lv - zk_mv_lv);
-        let z_mv = 0.5 * (zk_mv_lv + zk_hv_mv - zk_hv_lv);
-        let z_lv = 0.5 * (zk_hv_lv + zk_mv_lv - zk_hv_mv);
+        Ok(star_impedances(zk_hv_mv, zk_hv_lv, zk_mv_lv))
+    }
+
+    /// Impedances of the equivalent star circuit without correction factors, referred to
+    /// the given side.
+    pub fn uncorrected_impedance(
+        &self,
+        side: TransformerSide,
+    ) -> Result<(Complex64, Complex64, Complex64)> {
+        let z_hv_mv = self.side_impedance(side, TransformerSides::HvMv, None)?;
+
+        let z_hv_lv = self.side_impedance(side, TransformerSides::HvLv, None)?;
+
+        let z_mv_lv = self.side_impedance(side, TransformerSides::MvLv, None)?;
+
+        Ok(star_impedances(z_hv_mv, z_hv_lv, z_mv_lv))
+    }
+
+    /// Corrected impedances of the equivalent star circuit, each referred to the side of its
+    /// own winding.
+    pub fn winding_impedances(
+        &self,
+        busbar_index: &BusbarIndex<N>,
+    ) -> Result<(Complex64, Complex64, Complex64)> {
+        let (z_hv, _, _) = self.impedance(TransformerSide::HV, busbar_index)?;
+        let (_, z_mv, _) = self.impedance(TransformerSide::MV, busbar_index)?;
+        let (_, _, z_lv) = self.impedance(TransformerSide::LV, busbar_index)?;
+
+        Ok((z_hv, z_mv, z_lv))
+    }
+
+    /// Impedances of the equivalent star circuit without correction factors, each referred
+    /// to the side of its own winding.
+    pub fn uncorrected_winding_impedances(&self) -> Result<(Complex64, Complex64, Complex64)> {
+        let (z_hv, _, _) = self.uncorrected_impedance(TransformerSide::HV)?;
+        let (_, z_mv, _) = self.uncorrected_impedance(TransformerSide::MV)?;
+        let (_, _, z_lv) = self.uncorrected_impedance(TransformerSide::LV)?;
 
         Ok((z_hv, z_mv, z_lv))
     }
@@ -124,10 +159,10 @@ impl<N: Clone + Default + Eq + core::hash::Hash> ThreeWindingTransformer<N> {
         &self,
         side: TransformerSide,
         sides: TransformerSides,
-        busbar_index: &BusbarIndex<N>,
+        busbar_index: Option<&BusbarIndex<N>>,
     ) -> Result<Complex64> {
         let ur = match side {
-            TransformerSide::HV => self.ur_hv * 1e3,
+            TransformerSide::HV => self.ur_hv_tap() * 1e3,
             TransformerSide::MV => self.ur_mv * 1e3,
             TransformerSide::LV => self.ur_lv * 1e3,
         };
@@ -159,6 +194,10 @@ impl<N: Clone + Default + Eq + core::hash::Hash> ThreeWindingTransformer<N> {
 
         let z = cmplx!(urr / 100.0, uxr / 100.0) * cmplx!(ur.sq() / sr); // (10)
 
+        let Some(busbar_index) = busbar_index else {
+            return Ok(z);
+        };
+
         let mut cmax = voltage_correction_factor(self.ur_hv, false, true);
         // if let Some(node_hv) = self.node_hv.as_ref() {
         if let Some(busbar) = busbar_index.busbar(&self.node_hv) {
@@ -176,4 +215,37 @@ impl<N: Clone + Default + Eq + core::hash::Hash> ThreeWindingTransformer<N> {
 
         Ok(zk)
     }
+
+    /// Rated voltage of the high-voltage winding (kV) at the position of an off-load tap
+    /// changer.
+    pub fn ur_hv_tap(&self) -> f64 {
+        match &self.tap {
+            Some(tap) => self.ur_hv * (1.0 + tap.permanent()),
+            None => self.ur_hv,
+        }
+    }
+
+    /// Transformation ratios between the high-voltage and the medium and low-voltage sides
+    /// at the selected tap position.
+    pub fn ratios(&self, selection: TapSelection) -> (f64, f64) {
+        let ur_hv = match &self.tap {
+            Some(tap) => self.ur_hv * tap.factor(tap.select(selection)),
+            None => self.ur_hv,
+        };
+        (ur_hv / self.ur_mv, ur_hv / self.ur_lv)
+    }
+}
+
+/// Impedances of the equivalent star circuit from the short-circuit impedances between the
+/// high and medium, high and low, and medium and low-voltage sides (10a-c).
+fn star_impedances(
+    z_hv_mv: Complex64,
+    z_hv_lv: Complex64,
+    z_mv_lv: Complex64,
+) -> (Complex64, Complex64, Complex64) {
+    let z_hv = 0.5 * (z_hv_mv + z_hv_lv - z_mv_lv);
+    let z_mv = 0.5 * (z_mv_lv + z_hv_mv - z_hv_lv);
+    let z_lv = 0.5 * (z_hv_lv + z_mv_lv - z_hv_mv);
+
+    (z_hv, z_mv, z_lv)
 }
@@ -0,0 +1,121 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::factors::m;
+use crate::fault::Fault;
+
+/// Instantaneous short-circuit current of a three-phase short circuit starting at the
+/// voltage zero of the phase, giving the maximum d.c. component.
+///
+/// The symmetrical a.c. component decays exponentially from Ik'' to Ik, through the
+/// breaking current Ib at the minimum time delay `tmin`.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct Waveform {
+    /// Initial symmetrical short-circuit current (kA).
+    pub ikss: f64,
+
+    /// Symmetrical short-circuit breaking current (kA).
+    pub ib: f64,
+
+    /// Steady-state short-circuit current (kA).
+    pub ik: f64,
+
+    /// Minimum time delay (s) > 0 at which the a.c. component equals the breaking current.
+    pub tmin: f64,
+
+    /// D.c. component at the minimum time delay (kA).
+    pub idc: f64,
+
+    /// R/X ratio of the short-circuit impedance for the d.c. component.
+    pub rx: f64,
+
+    /// Factor κ of the peak short-circuit current, ip / (sqrt(2) Ik''), for the heat effect
+    /// of the d.c. component.
+    pub kappa: f64,
+
+    /// Nominal frequency (Hz).
+    pub f: f64,
+}
+
+impl Waveform {
+    /// Waveform of the short-circuit currents of `fault` for the minimum time delay `tmin`
+    /// (s) > 0 and the nominal frequency `f` (Hz). The d.c. component and its R/X ratio are
+    /// those of the asymmetrical breaking current and the factor κ is taken from the larger
+    /// of the peak currents of methods B and C.
+    pub fn new<N: Default>(fault: &Fault<N>, tmin: f64, f: f64) -> Self {
+        Waveform {
+            ikss: fault.ikss,
+            ib: fault.ib,
+            ik: fault.ik,
+            tmin,
+            idc: fault.idc,
+            rx: fault.rx,
+            kappa: fault.ip50.max(fault.ip20) / (2.0f64.sqrt() * fault.ikss),
+            f,
+        }
+    }
+
+    /// Ratio of the decaying part of the a.c. component at `tmin` to its initial value.
+    fn decay(&self) -> f64 {
+        if self.ikss > self.ik {
+            ((self.ib - self.ik) / (self.ikss - self.ik)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// R.m.s. value of the symmetrical a.c. component (kA) at time `t` (s).
+    pub fn ac(&self, t: f64) -> f64 {
+        self.ik + (self.ikss - self.ik) * self.decay().powf(t / self.tmin)
+    }
+
+    /// D.c. component (kA) at time `t` (s), decaying with the R/X ratio through the value
+    /// at the minimum time delay (64).
+    pub fn dc(&self, t: f64) -> f64 {
+        let omega = 2.0 * PI * self.f;
+        self.idc * (-omega * (t - self.tmin) * self.rx).exp()
+    }
+
+    /// Thermal equivalent short-circuit current (kA) for the duration `tk` (s) of the
+    /// short-circuit current (102). The factor n is obtained by integrating the heat effect
+    /// of the decaying a.c. component.
+    pub fn ith(&self, tk: f64) -> f64 {
+        let m = m(self.kappa, self.f, tk);
+
+        // Iac(t) = Ik + dI exp(-t / T)
+        let di = self.ikss - self.ik;
+        let decay = self.decay();
+        let n = if decay >= 1.0 {
+            1.0
+        } else if decay <= 0.0 {
+            (self.ik / self.ikss).powi(2)
+        } else {
+            let tau = -self.tmin / decay.ln();
+            let i2 = self.ik.powi(2) * tk
+                + 2.0 * self.ik * di * tau * (1.0 - (-tk / tau).exp())
+                + di.powi(2) * tau / 2.0 * (1.0 - (-2.0 * tk / tau).exp());
+            i2 / (self.ikss.powi(2) * tk)
+        };
+
+        self.ikss * (m + n).sqrt()
+    }
+
+    /// Instantaneous short-circuit current (kA) at time `t` (s).
+    pub fn current(&self, t: f64) -> f64 {
+        let omega = 2.0 * PI * self.f;
+        self.dc(t) - 2.0f64.sqrt() * self.ac(t) * (omega * t).cos()
+    }
+
+    /// Samples the instantaneous short-circuit current over `duration` (s) at `rate`
+    /// samples per second. Returns the time (s) and the current (kA) of each sample.
+    pub fn sample(&self, duration: f64, rate: f64) -> Vec<(f64, f64)> {
+        let n = (duration * rate).floor() as usize;
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / rate;
+                (t, self.current(t))
+            })
+            .collect()
+    }
+}
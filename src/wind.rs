@@ -0,0 +1,95 @@
+use anyhow::{format_err, Result};
+use derive_builder::Builder;
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+
+use crate::math::SQRT_3;
+use crate::traits::CurrentSource;
+use crate::{cmplx, traits::Sq};
+
+/// Wind power station unit with doubly fed asynchronous generator, including the unit
+/// transformer.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into, strip_option))]
+pub struct DoublyFedWindUnit<N: Default> {
+    /// Connection point on the high-voltage side of the unit transformer.
+    pub node: N,
+
+    /// Rated voltage of the unit transformer on the high-voltage side (kV).
+    pub ur: f64,
+
+    /// Factor for the calculation of the peak short-circuit current, given by the manufacturer
+    /// and referred to the high-voltage side of the unit transformer.
+    #[builder(default = "1.7")]
+    pub kappa: f64,
+
+    /// Highest instantaneous value of the current in case of a three-phase short circuit at the
+    /// high-voltage side of the unit transformer (kA).
+    pub iwdmax: f64,
+
+    /// Maximum steady-state short-circuit current (kA).
+    pub ikwdmax: f64,
+
+    /// R/X ratio of the short-circuit impedance.
+    #[builder(default = "0.1")]
+    pub rx: f64,
+}
+
+impl<N: Clone + Default> DoublyFedWindUnit<N> {
+    pub fn new() -> DoublyFedWindUnitBuilder<N> {
+        DoublyFedWindUnitBuilder::default()
+    }
+
+    /// Short-circuit impedance of the unit on the high-voltage side of the unit transformer.
+    pub fn impedance(&self) -> Result<Complex64> {
+        if self.iwdmax == 0.0 {
+            return Err(format_err!("iWDmax must be specified"));
+        }
+        let zwd = (2.0f64.sqrt() * self.kappa * self.ur) / (SQRT_3 * self.iwdmax);
+
+        let xwd = zwd / (1.0 + self.rx.sq()).sqrt();
+        let rwd = self.rx * xwd;
+
+        Ok(cmplx!(rwd, xwd))
+    }
+
+    /// Maximum steady-state short-circuit current (kA).
+    pub fn steady_state_current(&self) -> f64 {
+        self.ikwdmax
+    }
+}
+
+/// Wind power station unit with full size converter, represented by a current source.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Builder)]
+#[builder(default, setter(into, strip_option))]
+pub struct FullConverterWindUnit<N: Default> {
+    /// Connection point on the high-voltage side of the unit transformer.
+    pub node: N,
+
+    /// Source current of the unit in case of a three-phase short circuit, given by the
+    /// manufacturer (kA).
+    pub iskwf: f64,
+
+    /// Maximum steady-state short-circuit current (kA).
+    pub ikwfmax: f64,
+}
+
+impl<N: Clone + Default> FullConverterWindUnit<N> {
+    pub fn new() -> FullConverterWindUnitBuilder<N> {
+        FullConverterWindUnitBuilder::default()
+    }
+}
+
+impl<N: Default> CurrentSource<N> for FullConverterWindUnit<N> {
+    fn node(&self) -> &N {
+        &self.node
+    }
+
+    fn isk(&self) -> f64 {
+        self.iskwf
+    }
+
+    fn ikmax(&self) -> f64 {
+        self.ikwfmax
+    }
+}